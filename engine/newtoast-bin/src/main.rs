@@ -1,3 +1,68 @@
+use std::path::{Path, PathBuf};
+
 fn main() {
-    newtoast_core::engine_main();
+    let mut dotnet_info = false;
+    let mut interop: Option<(PathBuf, bool)> = None;
+    let mut manifest_path: Option<PathBuf> = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dotnet-info" => dotnet_info = true,
+            "--generate-interop" | "--check-interop" => match args.next() {
+                Some(path) => interop = Some((path.into(), arg == "--check-interop")),
+                None => fail(&format!("{} needs the path of the generated C# file", arg)),
+            },
+            "--manifest" => match args.next() {
+                Some(path) => manifest_path = Some(path.into()),
+                None => fail("--manifest needs a path"),
+            },
+            _ if manifest_path.is_none() && !arg.starts_with("--") => manifest_path = Some(arg.into()),
+            _ => fail(&format!("unexpected argument {}", arg)),
+        }
+    }
+
+    if let Some((path, check_only)) = interop {
+        generate_interop(&path, check_only);
+        return;
+    }
+
+    let result = if dotnet_info {
+        newtoast_core::DotnetEnvironmentInfo::query()
+            .map(|info| print!("{}", info))
+    } else {
+        let manifest = newtoast_core::ProjectManifest::locate(manifest_path.as_deref())
+            .unwrap_or_else(|e| fail(&e.to_string()));
+        newtoast_core::engine_main(&manifest)
+    };
+
+    if let Err(e) = result {
+        fail(&e.to_string());
+    }
+}
+
+/// Writes the C# side of the native API, or with `check_only` fails if the checked-in copy is stale
+fn generate_interop(path: &Path, check_only: bool) {
+    let result = if check_only {
+        newtoast_core::is_csharp_interop_current(path).map(|current| {
+            if !current {
+                fail(&format!("{} is stale, regenerate it with `just generate-interop`", path.display()));
+            }
+        })
+    } else {
+        newtoast_core::write_csharp_interop(path).map(|changed| {
+            if changed {
+                println!("Generated {}", path.display());
+            }
+        })
+    };
+
+    if let Err(e) = result {
+        fail(&format!("{}: {}", path.display(), e));
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("newtoast: {}", message);
+    std::process::exit(1);
 }
@@ -1,5 +1,7 @@
 // use build_print::println as bpln;
 
+use std::{fmt::Write, path::Path};
+
 fn main() {
     // bpln!("------------------------");
     // bpln!("-   ENV:   -------------");
@@ -20,8 +22,49 @@ fn main() {
         }
     };
 
-    println!("cargo:rustc-link-lib=dylib=nethost");
+    // cargo sets CARGO_FEATURE_<name> for build scripts
+    let dotnet = std::env::var_os("CARGO_FEATURE_DOTNET").is_some();
+    if dotnet {
+        println!("cargo:rustc-link-lib=dylib=nethost");
+    }
     println!("cargo:rustc-link-lib=dylib=SDL3");
     // Re-run build.rs if this file or the library path changes
     println!("cargo:rerun-if-changed=build.rs");
+
+    if dotnet {
+        write_embedded_assemblies();
+    }
+}
+
+// Generates the EMBEDDED_ASSEMBLIES table included by src/dotnet/embedded_assemblies.rs
+fn write_embedded_assemblies() {
+    println!("cargo:rerun-if-env-changed=NEWTOAST_EMBED_ASSEMBLIES");
+
+    let mut entries = String::new();
+    if let Some(paths) = std::env::var_os("NEWTOAST_EMBED_ASSEMBLIES") {
+        for path in std::env::split_paths(&paths).filter(|p| !p.as_os_str().is_empty()) {
+            let path = path.canonicalize()
+                .unwrap_or_else(|e| panic!("Can't embed assembly {}: {}", path.display(), e));
+            println!("cargo:rerun-if-changed={}", path.display());
+
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let symbols_path = path.with_extension("pdb");
+            let symbols = if symbols_path.exists() {
+                println!("cargo:rerun-if-changed={}", symbols_path.display());
+                format!("Some(include_bytes!({:?}))", symbols_path)
+            } else {
+                "None".to_owned()
+            };
+
+            writeln!(
+                entries,
+                "    EmbeddedAssembly {{ name: {:?}, assembly: include_bytes!({:?}), symbols: {} }},",
+                name, path, symbols
+            ).unwrap();
+        }
+    }
+
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let generated = format!("pub static EMBEDDED_ASSEMBLIES: &[EmbeddedAssembly] = &[\n{}];\n", entries);
+    std::fs::write(Path::new(&out_dir).join("embedded_assemblies.rs"), generated).unwrap();
 }
@@ -0,0 +1,68 @@
+//! A plain Rust `Game` spinning one triangle, builds without .NET:
+//! `cargo run -p newtoast_core --example rust_game --no-default-features`
+
+use glam::Vec2;
+use newtoast_core::{run_game, Color, Game, Id, Mesh2DData, ProjectManifest, RenderServer, Transform2D};
+
+const VERTEX_SHADER: &str = "#version 330 core
+uniform mat3 u_transform;
+in vec2 a_position;
+in vec4 a_color;
+out vec4 v_color;
+void main() {
+    v_color = a_color;
+    gl_Position = vec4((u_transform * vec3(a_position, 1.0)).xy, 0.0, 1.0);
+}";
+
+const FRAGMENT_SHADER: &str = "#version 330 core
+in vec4 v_color;
+out vec4 f_color;
+void main() {
+    f_color = v_color;
+}";
+
+
+struct Spinner {
+    renderable: Id,
+    angle: f32,
+}
+
+impl Game for Spinner {
+    type Error = std::convert::Infallible;
+
+    fn initialize(&mut self, render_server: &mut RenderServer) {
+        let shader = render_server.shader_2d_create(VERTEX_SHADER, FRAGMENT_SHADER)
+            .unwrap_or_else(|e| panic!("{}", e));
+        let material = render_server.material_2d_create();
+        render_server.material_2d_set_shader(material, shader)
+            .unwrap_or_else(|e| panic!("{}", e));
+
+        let mesh = render_server.mesh_2d_create(&Mesh2DData {
+            positions: &[Vec2::new(0.0, 0.5), Vec2::new(-0.5, -0.5), Vec2::new(0.5, -0.5)],
+            colors: Some(&[Color::new(1.0, 0.0, 0.0, 1.0), Color::new(0.0, 1.0, 0.0, 1.0), Color::new(0.0, 0.0, 1.0, 1.0)]),
+            ..Default::default()
+        }, false).unwrap_or_else(|e| panic!("{}", e));
+
+        self.renderable = render_server.renderable_2d_create();
+        render_server.renderable_2d_add_surface(self.renderable, material, mesh);
+    }
+
+    fn update(&mut self, render_server: &mut RenderServer, delta_time: f64) {
+        self.angle += delta_time as f32;
+        let transform = Transform2D::from_scale_angle_translation(Vec2::ONE, self.angle, Vec2::ZERO);
+        render_server.renderable_2d_set_transform(self.renderable, transform);
+    }
+
+    fn draw(&mut self, _render_server: &mut RenderServer) {}
+
+    fn shutdown(&mut self) {}
+}
+
+fn main() {
+    // the manifest is optional here, only its [engine] and [game] sections apply
+    let config = ProjectManifest::locate(None)
+        .map(|manifest| manifest.engine_config())
+        .unwrap_or_default();
+
+    let Ok(()) = run_game(&config, &mut Spinner { renderable: 0, angle: 0.0 });
+}
@@ -0,0 +1,91 @@
+/// Declares a table of `[UnmanagedCallersOnly]` static methods on one managed type
+///
+/// ```ignore
+/// managed_bindings! {
+///     pub struct GameBindings in "Game.Callbacks, Game" {
+///         @main_thread pub fn on_window_resized = "OnWindowResized"(width: i32, height: i32);
+///         fn get_score = "GetScore"() -> i64;
+///     }
+/// }
+/// ```
+///
+/// Generates the struct, a `resolve(&DotnetContext)` constructor that tries every method and
+/// reports all the ones it couldn't find at once, and a typed method per entry. Those methods
+/// are safe to call, so each signature here has to match its managed counterpart exactly. They
+/// return `DotnetError::ContextClosed` instead of calling once the context is dropping.
+///
+/// Entries marked `@main_thread` take a `&MainThread` first, everything else may be called from
+/// any thread, so only leave the marker off methods whose managed side is thread-safe.
+macro_rules! managed_bindings {
+    (
+        $(#[$struct_meta:meta])*
+        $struct_vis:vis struct $name:ident in $type_name:literal {
+            $(
+                $(#[$fn_meta:meta])*
+                $(@$affinity:ident)? $fn_vis:vis fn $fn_name:ident = $method:literal ( $($arg:ident : $arg_ty:ty),* $(,)? ) $(-> $ret:ty)?;
+            )*
+        }
+    ) => {
+        $(#[$struct_meta])*
+        $struct_vis struct $name {
+            $($fn_name: $crate::dotnet::DotnetFunctionPtr,)*
+        }
+
+        impl $name {
+            pub const MANAGED_TYPE_NAME: &'static str = $type_name;
+
+            pub fn resolve(dotnet: &$crate::dotnet::DotnetContext) -> Result<Self, $crate::dotnet::DotnetError> {
+                let mut missing = Vec::new();
+                $(
+                    let $fn_name = dotnet.get_unmanaged_fn_pointer($type_name, $method)
+                        .map_err(|e| missing.push(e))
+                        .ok();
+                )*
+
+                match ($($fn_name,)*) {
+                    ($(Some($fn_name),)*) => Ok(Self {
+                        $($fn_name,)*
+                    }),
+                    _ => Err($crate::dotnet::DotnetError::UnresolvedBindings {
+                        bindings: stringify!($name),
+                        type_name: $type_name,
+                        missing,
+                    }),
+                }
+            }
+
+            $(
+                $crate::dotnet::managed_bindings!(@method
+                    [$(#[$fn_meta])*] [$($affinity)?]
+                    $fn_vis fn $fn_name($($arg: $arg_ty),*) $(-> $ret)?
+                );
+            )*
+        }
+    };
+
+    (@method
+        [$(#[$fn_meta:meta])*] []
+        $fn_vis:vis fn $fn_name:ident($($arg:ident : $arg_ty:ty),*) $(-> $ret:ty)?
+    ) => {
+        $(#[$fn_meta])*
+        $fn_vis fn $fn_name(&self, $($arg: $arg_ty),*) -> Result<($($ret)?), $crate::dotnet::DotnetError> {
+            unsafe {
+                self.$fn_name.try_call::<fn($($arg_ty),*) $(-> $ret)?>(($($arg),*))
+            }
+        }
+    };
+
+    (@method
+        [$(#[$fn_meta:meta])*] [main_thread]
+        $fn_vis:vis fn $fn_name:ident($($arg:ident : $arg_ty:ty),*) $(-> $ret:ty)?
+    ) => {
+        $(#[$fn_meta])*
+        $fn_vis fn $fn_name(&self, _main_thread: &$crate::dotnet::MainThread, $($arg: $arg_ty),*) -> Result<($($ret)?), $crate::dotnet::DotnetError> {
+            unsafe {
+                self.$fn_name.try_call::<fn($($arg_ty),*) $(-> $ret)?>(($($arg),*))
+            }
+        }
+    };
+}
+
+pub(crate) use managed_bindings;
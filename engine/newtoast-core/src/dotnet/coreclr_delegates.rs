@@ -1 +1,5 @@
+use crate::ffi_char_t::char_t;
 
+// #define UNMANAGEDCALLERSONLY_METHOD ((const char_t*)-1)
+// Passed as the delegate type name to bind a method marked [UnmanagedCallersOnly]
+pub const UNMANAGEDCALLERSONLY_METHOD: *const char_t = usize::MAX as *const char_t;
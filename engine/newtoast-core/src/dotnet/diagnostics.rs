@@ -0,0 +1,24 @@
+use std::cell::RefCell;
+
+use crate::ffi_char_t::{char_t, char_t_ptr_to_string};
+
+
+// hostfxr registers error writers per thread, so the buffer is per thread as well
+thread_local! {
+    static HOSTFXR_DIAGNOSTICS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Error writer installed through `hostfxr_set_error_writer`
+///
+/// Each message is logged immediately and kept until the next `take_hostfxr_diagnostics`
+pub(super) unsafe extern "C" fn buffering_error_writer(message: *const char_t) {
+    if let Some(message) = char_t_ptr_to_string(message) {
+        println!("hostfxr: {}", message);
+        HOSTFXR_DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().push(message));
+    }
+}
+
+/// Drains the messages hostfxr wrote on the current thread
+pub fn take_hostfxr_diagnostics() -> Vec<String> {
+    HOSTFXR_DIAGNOSTICS.with(|diagnostics| std::mem::take(&mut *diagnostics.borrow_mut()))
+}
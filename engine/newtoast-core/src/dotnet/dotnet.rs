@@ -2,31 +2,117 @@
 // This file is intentionally left blank. Add your Rust code here for dotnet interop or related logic.
 
 use std::mem::transmute;
-use std::{ffi::OsString, os::windows::ffi::OsStrExt};
+use std::path::Path;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
 use libloading::{Library, Symbol};
 
 use crate::dotnet::hostfxr::HostfxrDelegate;
-use crate::ffi_char_t::char_t;
+use crate::dotnet::{claim_main_thread, diagnostics, MainThreadClaim, resolve_frameworks, DotnetError, HostfxrStatus, RuntimeConfig, RuntimeProperties, PROPERTY_APP_CONTEXT_BASE_DIRECTORY};
+use crate::ffi_char_t::{char_t, char_t_ptr_to_string, CharTString};
 
-use crate::dotnet::{hostfxr, nethost};
+use crate::dotnet::{coreclr_delegates, hostfxr, nethost};
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Debug)]
+struct ContextState {
+    alive: bool,
+    calls_in_flight: usize,
+}
+
+/// Shared between a DotnetContext and every pointer resolved through it, cleared when the context drops
+///
+/// Calls register themselves for their whole duration and `invalidate` waits for them, so a worker
+/// can't enter managed code while the main thread closes the host context. A call made while the
+/// context is closing fails instead of blocking, which keeps nested calls from deadlocking.
+#[derive(Clone, Debug)]
+struct ContextToken(Arc<(Mutex<ContextState>, Condvar)>);
+
+/// Keeps the context open until the call it was taken for returns
+struct CallGuard<'a>(&'a ContextToken);
+
+impl ContextToken {
+    fn new() -> Self {
+        Self(Arc::new((Mutex::new(ContextState { alive: true, calls_in_flight: 0 }), Condvar::new())))
+    }
+
+    fn state(&self) -> MutexGuard<'_, ContextState> {
+        self.0.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_alive(&self) -> bool {
+        self.state().alive
+    }
+
+    fn begin_call(&self) -> Option<CallGuard<'_>> {
+        let mut state = self.state();
+        if !state.alive {
+            return None;
+        }
+        state.calls_in_flight += 1;
+        Some(CallGuard(self))
+    }
+
+    /// Blocks until every call in flight has returned
+    fn invalidate(&self) {
+        let mut state = self.state();
+        state.alive = false;
+        while state.calls_in_flight > 0 {
+            state = self.0.1.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+impl Drop for CallGuard<'_> {
+    fn drop(&mut self) {
+        let mut state = self.0.state();
+        state.calls_in_flight -= 1;
+        if state.calls_in_flight == 0 {
+            self.0.0.1.notify_all();
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct DotnetFunctionPtr {
     fptr: *mut std::ffi::c_void,
+    context: ContextToken,
 }
 
-//HACK the odds this still meets Send/Sync requirements is probably about 6%
-// unsafe impl Send for DotnetFunctionPtr {}
-// unsafe impl Sync for DotnetFunctionPtr {}
+// The pointer targets a static [UnmanagedCallersOnly] method, whose stub attaches the calling
+// thread to the runtime, and the context token is locked. Whether the managed method itself is
+// safe to run off the main thread is up to the binding, see `MainThread`.
+unsafe impl Send for DotnetFunctionPtr {}
+unsafe impl Sync for DotnetFunctionPtr {}
 
 impl DotnetFunctionPtr {
+    /// False once the DotnetContext this was resolved from has been dropped
+    pub fn is_valid(&self) -> bool {
+        self.context.is_alive()
+    }
+
     pub unsafe fn reify<F: DotnetFunction>(&self) -> &F {
         &*(self.fptr as *const F)
     }
 
+    /// Panics if the owning DotnetContext has been dropped or is closing
+    ///
+    /// Only for callers that know the context outlives them, everything else, `managed_bindings!`
+    /// included, goes through `try_call`.
     pub unsafe fn call<F: DotnetFunction>(&self, args: F::Args) -> F::Output
     {
-        self.reify::<F>().call_dotnet(args)
+        match self.try_call::<F>(args) {
+            Ok(output) => output,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Fails with `ContextClosed` once the owning DotnetContext starts dropping, which in turn
+    /// waits for this call to return before closing the runtime
+    pub unsafe fn try_call<F: DotnetFunction>(&self, args: F::Args) -> Result<F::Output, DotnetError>
+    {
+        let Some(_call) = self.context.begin_call() else {
+            return Err(DotnetError::ContextClosed);
+        };
+        Ok(self.reify::<F>().call_dotnet(args))
     }
 }
 
@@ -113,8 +199,16 @@ dotnet_function_impls! {
 }
 
 
+/// An initialized hostfxr context with a loaded runtime
+///
+/// Dropping it closes the host context and invalidates every `DotnetFunctionPtr` resolved from it.
+/// The runtime itself can't be unloaded from a process, so a later `create_context` with a
+/// compatible runtimeconfig attaches to the same runtime again.
 pub struct DotnetContext<'lib> {
     hostfxr_handle: hostfxr::hostfxr_handle,
+    token: ContextToken,
+    // released after `drop` has closed the host context
+    _main_thread: MainThreadClaim,
 
     hostfxr_lib: &'lib Library,
     init_for_cmd_line: Symbol<'lib, hostfxr::hostfxr_initialize_for_dotnet_command_line_fn>,
@@ -122,6 +216,10 @@ pub struct DotnetContext<'lib> {
     get_delegate: Symbol<'lib, hostfxr::hostfxr_get_runtime_delegate_fn>,
     run_app: Symbol<'lib, hostfxr::hostfxr_run_app_fn>,
     close: Symbol<'lib, hostfxr::hostfxr_close_fn>,
+    set_error_writer: Symbol<'lib, hostfxr::hostfxr_set_error_writer_fn>,
+    previous_error_writer: Option<hostfxr::hostfxr_error_writer_fn>,
+    get_property: Symbol<'lib, hostfxr::hostfxr_get_runtime_property_value_fn>,
+    get_properties: Symbol<'lib, hostfxr::hostfxr_get_runtime_properties_fn>,
 
     delegate_get_fptr: hostfxr::get_function_pointer_fn,
     delegate_load_assembly: hostfxr::load_assembly_fn,
@@ -129,50 +227,189 @@ pub struct DotnetContext<'lib> {
 }
 
 impl<'lib> DotnetContext<'lib> {
-    pub fn load_assembly(&mut self, path: &str) {
+    /// Drains hostfxr's buffered error-writer output for this thread
+    pub fn take_diagnostics(&self) -> Vec<String> {
+        diagnostics::take_hostfxr_diagnostics()
+    }
+
+    /// Reads a runtime property, `None` if the property isn't set
+    pub fn get_runtime_property(&self, name: &str) -> Result<Option<String>, DotnetError> {
+        unsafe {
+            let name_native = CharTString::new(name);
+            let mut value: *const char_t = std::ptr::null();
+            let rc = (self.get_property)(self.hostfxr_handle, name_native.as_ptr(), &mut value);
+            match HostfxrStatus::check(rc) {
+                Ok(_) => Ok(char_t_ptr_to_string(value)),
+                Err(HostfxrStatus::HostPropertyNotFound) => Ok(None),
+                Err(status) => Err(DotnetError::RuntimePropertyFailed {
+                    name: name.to_owned(),
+                    status,
+                }),
+            }
+        }
+    }
+
+    /// All runtime properties of the context, in the order hostfxr reports them
+    pub fn get_runtime_properties(&self) -> Result<Vec<(String, String)>, DotnetError> {
+        unsafe {
+            let mut count = 0;
+            let rc = (self.get_properties)(
+                self.hostfxr_handle,
+                &mut count,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+            match HostfxrStatus::check(rc) {
+                Ok(_) | Err(HostfxrStatus::HostApiBufferTooSmall) => {},
+                Err(status) => return Err(DotnetError::GetRuntimePropertiesFailed { status }),
+            }
+
+            let mut keys: Vec<*const char_t> = vec![std::ptr::null(); count];
+            let mut values: Vec<*const char_t> = vec![std::ptr::null(); count];
+            let rc = (self.get_properties)(
+                self.hostfxr_handle,
+                &mut count,
+                keys.as_mut_ptr(),
+                values.as_mut_ptr(),
+            );
+            HostfxrStatus::check(rc)
+                .map_err(|status| DotnetError::GetRuntimePropertiesFailed { status })?;
+
+            // the buffers are owned by the host context, copy them out right away
+            let properties = keys.iter()
+                .zip(values.iter())
+                .take(count)
+                .map(|(&key, &value)| (
+                    char_t_ptr_to_string(key).unwrap_or_default(),
+                    char_t_ptr_to_string(value).unwrap_or_default(),
+                ))
+                .collect();
+            Ok(properties)
+        }
+    }
+
+    pub fn load_assembly(&mut self, path: &str) -> Result<(), DotnetError> {
         unsafe {
             let load_assembly = self.delegate_load_assembly;
             println!("Loading assembly from path:\n  {}", path);
-            let path_wide: Vec<u16> = OsString::from(path).encode_wide().chain(std::iter::once('\0' as u16)).collect();
+            let path_native = CharTString::new(path);
             let rc = load_assembly(
-                path_wide.as_ptr() as *const char_t,
+                path_native.as_ptr(),
                 std::ptr::null_mut(),
                 std::ptr::null_mut(),
             );
-            assert_eq!(rc, 0);
+            HostfxrStatus::check(rc)
+                .map_err(|status| DotnetError::LoadAssemblyFailed {
+                    path: path.to_owned(),
+                    status,
+                })?;
+            Ok(())
         }
     }
 
+    /// Loads an assembly (and optionally its PDB) from memory into the default load context
+    pub fn load_assembly_from_bytes(&mut self, assembly: &[u8], symbols: Option<&[u8]>) -> Result<(), DotnetError> {
+        unsafe {
+            let load_assembly_bytes = self.delegate_load_assembly_bytes;
+            println!("Loading assembly from memory ({} bytes)", assembly.len());
+            let (symbols_ptr, symbols_len) = match symbols {
+                Some(symbols) => (symbols.as_ptr(), symbols.len()),
+                None => (std::ptr::null(), 0),
+            };
+            let rc = load_assembly_bytes(
+                assembly.as_ptr() as *const std::ffi::c_void,
+                assembly.len(),
+                symbols_ptr as *const std::ffi::c_void,
+                symbols_len,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+            HostfxrStatus::check(rc)
+                .map_err(|status| DotnetError::LoadAssemblyBytesFailed {
+                    size: assembly.len(),
+                    status,
+                })?;
+            Ok(())
+        }
+    }
+
+    /// Binds a static method through a delegate type, `delegate_type_name` is assembly qualified
     pub fn get_fn_pointer<'dn>(
         &'dn self,
         type_name: &str,
         method_name: &str,
         delegate_type_name: &str
-    ) -> DotnetFunctionPtr {
+    ) -> Result<DotnetFunctionPtr, DotnetError> {
+        self.get_fn_pointer_impl(type_name, method_name, Some(delegate_type_name))
+    }
+
+    /// Binds a static method marked `[UnmanagedCallersOnly]`, no delegate type or marshalling stub involved
+    pub fn get_unmanaged_fn_pointer<'dn>(
+        &'dn self,
+        type_name: &str,
+        method_name: &str
+    ) -> Result<DotnetFunctionPtr, DotnetError> {
+        self.get_fn_pointer_impl(type_name, method_name, None)
+    }
+
+    fn get_fn_pointer_impl(
+        &self,
+        type_name: &str,
+        method_name: &str,
+        delegate_type_name: Option<&str>
+    ) -> Result<DotnetFunctionPtr, DotnetError> {
         unsafe {
             let mut fptr = std::ptr::null_mut();
             let get_fptr = self.delegate_get_fptr;
-            let type_name_wide: Vec<u16> = OsString::from(type_name).encode_wide().chain(std::iter::once('\0' as u16)).collect();
-            let method_name_wide: Vec<u16> = OsString::from(method_name).encode_wide().chain(std::iter::once('\0' as u16)).collect();
-            let delegate_type_name_wide: Vec<u16> = OsString::from(delegate_type_name).encode_wide().chain(std::iter::once('\0' as u16)).collect();
-            get_fptr(
-                type_name_wide.as_ptr() as *const char_t,
-                method_name_wide.as_ptr() as *const char_t,
-                delegate_type_name_wide.as_ptr() as *const char_t,
+            let type_name_native = CharTString::new(type_name);
+            let method_name_native = CharTString::new(method_name);
+            let delegate_type_name_native = delegate_type_name.map(CharTString::new);
+            let rc = get_fptr(
+                type_name_native.as_ptr(),
+                method_name_native.as_ptr(),
+                delegate_type_name_native.as_ref()
+                    .map_or(coreclr_delegates::UNMANAGEDCALLERSONLY_METHOD, |name| name.as_ptr()),
                 std::ptr::null_mut(),
                 std::ptr::null_mut(),
                 &mut fptr
             );
-            assert!(!fptr.is_null());
 
-            DotnetFunctionPtr {
+            let status = HostfxrStatus::check(rc)
+                .and_then(|status| if fptr.is_null() { Err(status) } else { Ok(status) });
+            if let Err(status) = status {
+                return Err(DotnetError::GetFunctionPointerFailed {
+                    type_name: type_name.to_owned(),
+                    method_name: method_name.to_owned(),
+                    delegate_type_name: delegate_type_name
+                        .unwrap_or("UNMANAGEDCALLERSONLY_METHOD")
+                        .to_owned(),
+                    status,
+                });
+            }
+
+            Ok(DotnetFunctionPtr {
                 fptr,
+                context: self.token.clone(),
+            })
+        }
+    }
+}
+
+impl<'lib> Drop for DotnetContext<'lib> {
+    fn drop(&mut self) {
+        self.token.invalidate();
+        unsafe {
+            let rc = (self.close)(self.hostfxr_handle);
+            if let Err(status) = HostfxrStatus::check(rc) {
+                println!("hostfxr_close failed: {}", status);
             }
+            (self.set_error_writer)(self.previous_error_writer);
         }
     }
 }
 
-pub fn load_hostfxr() -> Library {
+/// Asks nethost where the hostfxr library of the active dotnet install is
+pub fn get_hostfxr_path() -> Result<String, DotnetError> {
     let mut buffer: [char_t; 1024] = [0; 1024];
     let mut buffer_size = buffer.len();
     unsafe {
@@ -181,72 +418,193 @@ pub fn load_hostfxr() -> Library {
             &mut buffer_size,
             std::ptr::null(),
         );
-        let mut path=  String::from_utf16(&buffer).unwrap();
-        path.truncate(buffer_size);
-        println!("hostfxr path:\n  {}", path);
+        HostfxrStatus::check(rc)
+            .map_err(|status| DotnetError::HostfxrPathNotFound { status })?;
+
+        Ok(char_t_ptr_to_string(buffer.as_ptr()).unwrap_or_default())
+    }
+}
+
+pub fn load_hostfxr() -> Result<Library, DotnetError> {
+    let path = get_hostfxr_path()?;
+    println!("hostfxr path:\n  {}", path);
+
+    unsafe {
+        libloading::Library::new(&path)
+            .map_err(|e| DotnetError::HostfxrLoadFailed {
+                path,
+                message: e.to_string(),
+            })
+    }
+}
+
+pub(super) unsafe fn get_hostfxr_export<'lib, T>(
+    hostfxr_lib: &'lib Library,
+    symbol: &'static str
+) -> Result<Symbol<'lib, T>, DotnetError> {
+    hostfxr_lib.get(symbol.as_bytes())
+        .map_err(|e| DotnetError::MissingHostfxrExport {
+            symbol,
+            message: e.to_string(),
+        })
+}
+
+unsafe fn get_runtime_delegate<T>(
+    get_delegate: &Symbol<hostfxr::hostfxr_get_runtime_delegate_fn>,
+    hostfxr_handle: hostfxr::hostfxr_handle,
+    delegate: HostfxrDelegate,
+) -> Result<T, DotnetError> {
+    let mut delegate_out: *mut std::ffi::c_void = std::ptr::null_mut();
+    let rc = get_delegate(
+        hostfxr_handle,
+        delegate as i32,
+        &mut delegate_out,
+    );
+    let status = HostfxrStatus::check(rc)
+        .and_then(|status| if delegate_out.is_null() { Err(status) } else { Ok(status) });
+    if let Err(status) = status {
+        return Err(DotnetError::GetDelegateFailed {
+            delegate,
+            status,
+            diagnostics: diagnostics::take_hostfxr_diagnostics(),
+        });
+    }
+
+    Ok(std::mem::transmute_copy(&delegate_out))
+}
 
-        let lib = libloading::Library::new(&path).unwrap();
 
-        lib
+unsafe fn set_runtime_property(
+    set_property: &Symbol<hostfxr::hostfxr_set_runtime_property_value_fn>,
+    hostfxr_handle: hostfxr::hostfxr_handle,
+    name: &str,
+    value: Option<&str>,
+) -> Result<(), DotnetError> {
+    let name_native = CharTString::new(name);
+    let value_native = value.map(CharTString::new);
+    let rc = set_property(
+        hostfxr_handle,
+        name_native.as_ptr(),
+        value_native.as_ref().map_or(std::ptr::null(), |v| v.as_ptr()),
+    );
+    HostfxrStatus::check(rc)
+        .map_err(|status| DotnetError::RuntimePropertyFailed {
+            name: name.to_owned(),
+            status,
+        })?;
+    Ok(())
+}
+
+
+/// Like `create_context`, with a runtimeconfig generated from `config` instead of read from disk
+///
+/// The generated file lives in the temp directory, so `AppContext.BaseDirectory` is pointed at
+/// `base_directory` unless `config` or `properties` already sets it.
+pub fn create_context_with_config<'lib>(
+    hostfxr_lib: &'lib Library,
+    config: &RuntimeConfig,
+    base_directory: &Path,
+    properties: &RuntimeProperties,
+) -> Result<DotnetContext<'lib>, DotnetError> {
+    let runtimeconfig = config.write_temp()?;
+
+    let mut properties_with_base = RuntimeProperties::new();
+    // properties set after init override configProperties, so only fill in a missing value
+    let configures_base = config.properties.iter().any(|(name, _)| name == PROPERTY_APP_CONTEXT_BASE_DIRECTORY);
+    if !configures_base {
+        properties_with_base.app_context_base_directory(base_directory);
+    }
+    for (name, value) in properties.iter() {
+        match value {
+            Some(value) => properties_with_base.set(name, value),
+            None => properties_with_base.remove(name),
+        };
     }
+
+    // hostfxr is done with the file once the context is initialized
+    create_context(hostfxr_lib, &runtimeconfig.path().to_string_lossy(), &properties_with_base)
 }
 
+/// Initializes hostfxr from a runtimeconfig, applies `properties` and starts the runtime
+pub fn create_context<'lib>(
+    hostfxr_lib: &'lib Library,
+    runtimeconfig_path: &str,
+    properties: &RuntimeProperties,
+) -> Result<DotnetContext<'lib>, DotnetError> {
+    let main_thread = claim_main_thread()?;
 
-pub fn create_context<'lib>(hostfxr_lib: &'lib Library, runtimeconfig_path: &str) -> DotnetContext<'lib> {
     unsafe {
-        // TODO handle rc
-        let init_for_cmd_line: libloading::Symbol<hostfxr::hostfxr_initialize_for_dotnet_command_line_fn>
-            = hostfxr_lib.get(b"hostfxr_initialize_for_dotnet_command_line").unwrap();
-        let init_with_config: libloading::Symbol<hostfxr::hostfxr_initialize_for_runtime_config_fn>
-            = hostfxr_lib.get(b"hostfxr_initialize_for_runtime_config").unwrap();
-        let get_delegate: libloading::Symbol<hostfxr::hostfxr_get_runtime_delegate_fn>
-            = hostfxr_lib.get(b"hostfxr_get_runtime_delegate").unwrap();
-        let run_app: libloading::Symbol<hostfxr::hostfxr_run_app_fn>
-            = hostfxr_lib.get(b"hostfxr_run_app").unwrap();
-        let close: libloading::Symbol<hostfxr::hostfxr_close_fn>
-            = hostfxr_lib.get(b"hostfxr_close").unwrap();
+        let init_for_cmd_line = get_hostfxr_export(hostfxr_lib, "hostfxr_initialize_for_dotnet_command_line")?;
+        let init_with_config: Symbol<hostfxr::hostfxr_initialize_for_runtime_config_fn>
+            = get_hostfxr_export(hostfxr_lib, "hostfxr_initialize_for_runtime_config")?;
+        let get_delegate = get_hostfxr_export(hostfxr_lib, "hostfxr_get_runtime_delegate")?;
+        let run_app = get_hostfxr_export(hostfxr_lib, "hostfxr_run_app")?;
+        let close: Symbol<hostfxr::hostfxr_close_fn>
+            = get_hostfxr_export(hostfxr_lib, "hostfxr_close")?;
+        let set_error_writer: Symbol<hostfxr::hostfxr_set_error_writer_fn>
+            = get_hostfxr_export(hostfxr_lib, "hostfxr_set_error_writer")?;
+        let get_property = get_hostfxr_export(hostfxr_lib, "hostfxr_get_runtime_property_value")?;
+        let set_property: Symbol<hostfxr::hostfxr_set_runtime_property_value_fn>
+            = get_hostfxr_export(hostfxr_lib, "hostfxr_set_runtime_property_value")?;
+        let get_properties = get_hostfxr_export(hostfxr_lib, "hostfxr_get_runtime_properties")?;
+
+        // anything left over belongs to an earlier context and was already logged
+        diagnostics::take_hostfxr_diagnostics();
+        let previous_error_writer = set_error_writer(Some(diagnostics::buffering_error_writer));
+
+        // a missing runtime otherwise only shows up as an opaque init failure
+        match resolve_frameworks(hostfxr_lib, runtimeconfig_path) {
+            Ok(Some(report)) => print!("{}", report),
+            Ok(None) => println!("hostfxr can't preflight framework resolution, skipping"),
+            Err(e) => {
+                set_error_writer(previous_error_writer);
+                return Err(e);
+            }
+        }
 
         let mut hostfxr_handle = std::ptr::null_mut();
-        let config_path: Vec<u16> = OsString::from(runtimeconfig_path).encode_wide().chain(std::iter::once('\0' as u16)).collect();
+        let config_path = CharTString::new(runtimeconfig_path);
         let rc = init_with_config(
-            config_path.as_ptr() as *const char_t,
+            config_path.as_ptr(),
             std::ptr::null_mut(),
             &mut hostfxr_handle
         );
-        assert_eq!(rc, 0);
-
-        //TODO error handling
-
-        let mut get_fptr_out: *mut std::ffi::c_void = std::ptr::null_mut();
-        let get_fptr_rc = get_delegate(
-            hostfxr_handle,
-            HostfxrDelegate::GetFunctionPointer as i32,
-            &mut get_fptr_out,
-        );
-        assert_eq!(get_fptr_rc, 0);
-        let delegate_get_fptr = std::mem::transmute(get_fptr_out);
+        if let Err(status) = HostfxrStatus::check(rc) {
+            set_error_writer(previous_error_writer);
+            return Err(DotnetError::InitializeFailed {
+                runtimeconfig_path: runtimeconfig_path.to_owned(),
+                status,
+                diagnostics: diagnostics::take_hostfxr_diagnostics(),
+            });
+        }
 
-        let mut load_assembly_out: *mut std::ffi::c_void = std::ptr::null_mut();
-        let load_assembly_rc = get_delegate(
-            hostfxr_handle,
-            HostfxrDelegate::LoadAssembly as i32,
-            &mut load_assembly_out
-        );
-        assert_eq!(load_assembly_rc, 0);
-        let delegate_load_assembly = std::mem::transmute(load_assembly_out);
+        let delegates = (|| {
+            // the runtime is loaded by the first get_delegate, properties have to go in before that
+            for (name, value) in properties.iter() {
+                set_runtime_property(&set_property, hostfxr_handle, name, value)?;
+            }
 
-        let mut load_assembly_bytes_out: *mut std::ffi::c_void = std::ptr::null_mut();
-        let load_assembly_bytes_rc = get_delegate(
-            hostfxr_handle,
-            HostfxrDelegate::LoadAssemblyBytes as i32,
-            &mut load_assembly_bytes_out
-        );
-        assert_eq!(load_assembly_bytes_rc, 0);
-        let delegate_load_assembly_bytes = std::mem::transmute(load_assembly_bytes_out);
+            Ok((
+                get_runtime_delegate(&get_delegate, hostfxr_handle, HostfxrDelegate::GetFunctionPointer)?,
+                get_runtime_delegate(&get_delegate, hostfxr_handle, HostfxrDelegate::LoadAssembly)?,
+                get_runtime_delegate(&get_delegate, hostfxr_handle, HostfxrDelegate::LoadAssemblyBytes)?,
+            ))
+        })();
+        let (delegate_get_fptr, delegate_load_assembly, delegate_load_assembly_bytes) = match delegates {
+            Ok(delegates) => delegates,
+            Err(e) => {
+                // the handle is only useful to a DotnetContext, don't leak it on failure
+                close(hostfxr_handle);
+                set_error_writer(previous_error_writer);
+                return Err(e);
+            }
+        };
 
 
-        DotnetContext {
+        Ok(DotnetContext {
             hostfxr_handle,
+            token: ContextToken::new(),
+            _main_thread: main_thread,
             hostfxr_lib,
 
             init_for_cmd_line,
@@ -254,10 +612,14 @@ pub fn create_context<'lib>(hostfxr_lib: &'lib Library, runtimeconfig_path: &str
             get_delegate,
             run_app,
             close,
+            set_error_writer,
+            previous_error_writer,
+            get_property,
+            get_properties,
 
             delegate_get_fptr,
             delegate_load_assembly,
             delegate_load_assembly_bytes,
-        }
+        })
     }
 }
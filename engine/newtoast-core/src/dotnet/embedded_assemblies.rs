@@ -0,0 +1,16 @@
+/// A managed assembly compiled into the executable
+///
+/// Populated by build.rs from `NEWTOAST_EMBED_ASSEMBLIES`, a path list (same separator as `PATH`)
+/// of .dll files to embed in load order. A .pdb next to a .dll is embedded along with it.
+pub struct EmbeddedAssembly {
+    pub name: &'static str,
+    pub assembly: &'static [u8],
+    pub symbols: Option<&'static [u8]>,
+}
+
+include!(concat!(env!("OUT_DIR"), "/embedded_assemblies.rs"));
+
+/// True when the build embedded its assemblies and loose files should be ignored
+pub fn has_embedded_assemblies() -> bool {
+    !EMBEDDED_ASSEMBLIES.is_empty()
+}
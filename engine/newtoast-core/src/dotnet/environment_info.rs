@@ -0,0 +1,125 @@
+use std::{fmt, os::raw::c_void};
+
+use libloading::{Library, Symbol};
+
+use crate::dotnet::{get_hostfxr_export, get_hostfxr_path, hostfxr, DotnetError, HostfxrStatus};
+use crate::ffi_char_t::char_t_ptr_to_string;
+
+
+#[derive(Clone, Debug)]
+pub struct DotnetSdkInfo {
+    pub version: String,
+    pub path: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct DotnetFrameworkInfo {
+    pub name: String,
+    pub version: String,
+    pub path: String,
+}
+
+/// Owned copy of what `hostfxr_get_dotnet_environment_info` reports, plus where hostfxr was found
+#[derive(Clone, Debug, Default)]
+pub struct DotnetEnvironmentInfo {
+    pub hostfxr_path: String,
+    pub hostfxr_version: String,
+    pub hostfxr_commit_hash: String,
+    pub sdks: Vec<DotnetSdkInfo>,
+    pub frameworks: Vec<DotnetFrameworkInfo>,
+}
+
+impl DotnetEnvironmentInfo {
+    /// Locates and loads hostfxr, then queries it
+    pub fn query() -> Result<Self, DotnetError> {
+        let hostfxr_path = get_hostfxr_path()?;
+        let hostfxr_lib = unsafe {
+            Library::new(&hostfxr_path)
+                .map_err(|e| DotnetError::HostfxrLoadFailed {
+                    path: hostfxr_path.clone(),
+                    message: e.to_string(),
+                })?
+        };
+
+        let mut info = Self::query_with(&hostfxr_lib)?;
+        info.hostfxr_path = hostfxr_path;
+        Ok(info)
+    }
+
+    /// Queries an already loaded hostfxr, `hostfxr_path` is left empty
+    pub fn query_with(hostfxr_lib: &Library) -> Result<Self, DotnetError> {
+        unsafe {
+            let get_info: Symbol<hostfxr::hostfxr_get_dotnet_environment_info_fn>
+                = get_hostfxr_export(hostfxr_lib, "hostfxr_get_dotnet_environment_info")?;
+
+            let mut info = Self::default();
+            let rc = get_info(
+                std::ptr::null(),
+                std::ptr::null_mut(),
+                collect_environment_info,
+                &mut info as *mut Self as *mut c_void,
+            );
+            HostfxrStatus::check(rc)
+                .map_err(|status| DotnetError::GetEnvironmentInfoFailed { status })?;
+
+            Ok(info)
+        }
+    }
+}
+
+// the structs are only valid for the duration of the callback, so everything is copied out here
+unsafe extern "C" fn collect_environment_info(
+    info: *const hostfxr::HostfxrDotnetEnvironmentInfo,
+    result_context: *mut c_void,
+) {
+    if info.is_null() || result_context.is_null() {
+        return;
+    }
+    let info = &*info;
+    let out = &mut *(result_context as *mut DotnetEnvironmentInfo);
+
+    out.hostfxr_version = char_t_ptr_to_string(info.hostfxr_version).unwrap_or_default();
+    out.hostfxr_commit_hash = char_t_ptr_to_string(info.hostfxr_commit_hash).unwrap_or_default();
+
+    if !info.sdks.is_null() {
+        out.sdks = std::slice::from_raw_parts(info.sdks, info.sdk_count)
+            .iter()
+            .map(|sdk| DotnetSdkInfo {
+                version: char_t_ptr_to_string(sdk.version).unwrap_or_default(),
+                path: char_t_ptr_to_string(sdk.path).unwrap_or_default(),
+            })
+            .collect();
+    }
+
+    if !info.frameworks.is_null() {
+        out.frameworks = std::slice::from_raw_parts(info.frameworks, info.framework_count)
+            .iter()
+            .map(|framework| DotnetFrameworkInfo {
+                name: char_t_ptr_to_string(framework.name).unwrap_or_default(),
+                version: char_t_ptr_to_string(framework.version).unwrap_or_default(),
+                path: char_t_ptr_to_string(framework.path).unwrap_or_default(),
+            })
+            .collect();
+    }
+}
+
+impl fmt::Display for DotnetEnvironmentInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "hostfxr:")?;
+        writeln!(f, "  path:    {}", self.hostfxr_path)?;
+        writeln!(f, "  version: {}", self.hostfxr_version)?;
+        writeln!(f, "  commit:  {}", self.hostfxr_commit_hash)?;
+
+        writeln!(f, "SDKs ({}):", self.sdks.len())?;
+        for sdk in &self.sdks {
+            writeln!(f, "  {} [{}]", sdk.version, sdk.path)?;
+        }
+
+        writeln!(f, "Frameworks ({}):", self.frameworks.len())?;
+        for framework in &self.frameworks {
+            writeln!(f, "  {} {} [{}]", framework.name, framework.version, framework.path)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,287 @@
+use std::fmt;
+
+use crate::dotnet::{hostfxr::HostfxrDelegate, FrameworkResolution};
+
+
+macro_rules! hostfxr_status_codes {
+    ($($name:ident = $code:literal,)*) => {
+        /// Status codes returned by hostfxr, hostpolicy and the managed hosting delegates
+        ///
+        /// See `src/native/corehost/error_codes.h` in dotnet/runtime
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        pub enum HostfxrStatus {
+            $($name,)*
+            Other(i32),
+        }
+
+        impl HostfxrStatus {
+            pub fn from_code(code: i32) -> Self {
+                match code as u32 {
+                    $($code => Self::$name,)*
+                    _ => Self::Other(code),
+                }
+            }
+
+            pub fn code(self) -> i32 {
+                match self {
+                    $(Self::$name => $code as u32 as i32,)*
+                    Self::Other(code) => code,
+                }
+            }
+
+            fn name(self) -> Option<&'static str> {
+                match self {
+                    $(Self::$name => Some(stringify!($name)),)*
+                    Self::Other(_) => None,
+                }
+            }
+        }
+    };
+}
+
+hostfxr_status_codes! {
+    Success = 0x00000000,
+    SuccessHostAlreadyInitialized = 0x00000001,
+    SuccessDifferentRuntimeProperties = 0x00000002,
+
+    InvalidArgFailure = 0x80008081,
+    CoreHostLibLoadFailure = 0x80008082,
+    CoreHostLibMissingFailure = 0x80008083,
+    CoreHostEntryPointFailure = 0x80008084,
+    CurrentHostFindFailure = 0x80008085,
+    CoreClrResolveFailure = 0x80008087,
+    CoreClrBindFailure = 0x80008088,
+    CoreClrInitFailure = 0x80008089,
+    CoreClrExeFailure = 0x8000808a,
+    ResolverInitFailure = 0x8000808b,
+    ResolverResolveFailure = 0x8000808c,
+    LibHostInitFailure = 0x8000808e,
+    LibHostSdkFindFailure = 0x80008091,
+    LibHostInvalidArgs = 0x80008092,
+    InvalidConfigFile = 0x80008093,
+    AppArgNotRunnable = 0x80008094,
+    AppHostExeNotBoundFailure = 0x80008095,
+    FrameworkMissingFailure = 0x80008096,
+    HostApiFailed = 0x80008097,
+    HostApiBufferTooSmall = 0x80008098,
+    AppPathFindFailure = 0x8000809a,
+    SdkResolveFailure = 0x8000809b,
+    FrameworkCompatFailure = 0x8000809c,
+    FrameworkCompatRetry = 0x8000809d,
+    BundleExtractionFailure = 0x8000809f,
+    BundleExtractionIOError = 0x800080a0,
+    LibHostDuplicateProperty = 0x800080a1,
+    HostApiUnsupportedVersion = 0x800080a2,
+    HostInvalidState = 0x800080a3,
+    HostPropertyNotFound = 0x800080a4,
+    HostIncompatibleConfig = 0x800080a5,
+    HostApiUnsupportedScenario = 0x800080a6,
+    HostFeatureDisabled = 0x800080a7,
+
+    // HRESULTs of exceptions thrown by the managed hosting delegates
+    FileNotFound = 0x80070002,
+    BadImageFormat = 0x8007000b,
+    InvalidArgument = 0x80070057,
+    InvalidOperation = 0x80131509,
+    MissingMethod = 0x80131513,
+    TypeLoad = 0x80131522,
+}
+
+impl HostfxrStatus {
+    pub fn is_success(self) -> bool {
+        self.code() >= 0
+    }
+
+    /// Maps a raw return code to `Ok` for any of the success codes
+    pub fn check(code: i32) -> Result<Self, Self> {
+        let status = Self::from_code(code);
+        if status.is_success() {
+            Ok(status)
+        } else {
+            Err(status)
+        }
+    }
+}
+
+impl fmt::Display for HostfxrStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{} (0x{:08X})", name, self.code() as u32),
+            None => write!(f, "0x{:08X}", self.code() as u32),
+        }
+    }
+}
+
+
+#[derive(Clone, Debug)]
+pub enum DotnetError {
+    HostfxrPathNotFound {
+        status: HostfxrStatus,
+    },
+    HostfxrLoadFailed {
+        path: String,
+        message: String,
+    },
+    MissingHostfxrExport {
+        symbol: &'static str,
+        message: String,
+    },
+    GetEnvironmentInfoFailed {
+        status: HostfxrStatus,
+    },
+    ResolveFrameworksFailed {
+        runtimeconfig_path: String,
+        status: HostfxrStatus,
+        diagnostics: Vec<String>,
+    },
+    MissingFrameworks {
+        runtimeconfig_path: String,
+        unresolved: Vec<FrameworkResolution>,
+        diagnostics: Vec<String>,
+    },
+    WriteRuntimeConfigFailed {
+        path: String,
+        message: String,
+    },
+    InitializeFailed {
+        runtimeconfig_path: String,
+        status: HostfxrStatus,
+        diagnostics: Vec<String>,
+    },
+    GetDelegateFailed {
+        delegate: HostfxrDelegate,
+        status: HostfxrStatus,
+        diagnostics: Vec<String>,
+    },
+    RuntimePropertyFailed {
+        name: String,
+        status: HostfxrStatus,
+    },
+    GetRuntimePropertiesFailed {
+        status: HostfxrStatus,
+    },
+    LoadAssemblyFailed {
+        path: String,
+        status: HostfxrStatus,
+    },
+    LoadAssemblyBytesFailed {
+        size: usize,
+        status: HostfxrStatus,
+    },
+    GetFunctionPointerFailed {
+        type_name: String,
+        method_name: String,
+        delegate_type_name: String,
+        status: HostfxrStatus,
+    },
+    ContextClosed,
+    NotMainThread,
+    ManagedHandlesNotInstalled,
+    CloneHandleFailed,
+    UnresolvedBindings {
+        bindings: &'static str,
+        type_name: &'static str,
+        missing: Vec<DotnetError>,
+    },
+    /// A managed lifecycle call threw, the exception was caught on the managed side
+    ManagedException {
+        method: &'static str,
+        message: String,
+        stack_trace: String,
+    },
+}
+
+impl fmt::Display for DotnetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HostfxrPathNotFound { status } =>
+                write!(f, "could not locate hostfxr: {}", status),
+            Self::HostfxrLoadFailed { path, message } =>
+                write!(f, "could not load hostfxr from \"{}\": {}", path, message),
+            Self::MissingHostfxrExport { symbol, message } =>
+                write!(f, "hostfxr does not export {}: {}", symbol, message),
+            Self::GetEnvironmentInfoFailed { status } =>
+                write!(f, "failed to query the dotnet environment: {}", status),
+            Self::ResolveFrameworksFailed { runtimeconfig_path, status, diagnostics } => {
+                write!(f, "failed to resolve frameworks for \"{}\": {}", runtimeconfig_path, status)?;
+                write_diagnostics(f, diagnostics)
+            },
+            Self::MissingFrameworks { runtimeconfig_path, unresolved, diagnostics } => {
+                write!(f, "\"{}\" needs .NET frameworks that are not installed:", runtimeconfig_path)?;
+                for framework in unresolved {
+                    write!(f, "\n  {} {}", framework.name, framework.requested_version)?;
+                }
+                write!(f, "\nInstall the matching runtime from https://dotnet.microsoft.com/download/dotnet")?;
+                write_diagnostics(f, diagnostics)
+            },
+            Self::WriteRuntimeConfigFailed { path, message } =>
+                write!(f, "failed to write runtimeconfig \"{}\": {}", path, message),
+            Self::InitializeFailed { runtimeconfig_path, status, diagnostics } => {
+                write!(f, "failed to initialize runtime from \"{}\": {}", runtimeconfig_path, status)?;
+                write_diagnostics(f, diagnostics)
+            },
+            Self::GetDelegateFailed { delegate, status, diagnostics } => {
+                write!(f, "failed to get runtime delegate {:?}: {}", delegate, status)?;
+                write_diagnostics(f, diagnostics)
+            },
+            Self::RuntimePropertyFailed { name, status } =>
+                write!(f, "failed to access runtime property {}: {}", name, status),
+            Self::GetRuntimePropertiesFailed { status } =>
+                write!(f, "failed to list runtime properties: {}", status),
+            Self::LoadAssemblyFailed { path, status } =>
+                write!(f, "failed to load assembly \"{}\": {}", path, status),
+            Self::LoadAssemblyBytesFailed { size, status } =>
+                write!(f, "failed to load assembly from memory ({} bytes): {}", size, status),
+            Self::GetFunctionPointerFailed { type_name, method_name, delegate_type_name, status } =>
+                write!(
+                    f,
+                    "failed to resolve {}::{} (delegate {}): {}",
+                    type_name, method_name, delegate_type_name, status
+                ),
+            Self::ContextClosed =>
+                write!(f, "called into managed code after its DotnetContext was dropped"),
+            Self::NotMainThread =>
+                write!(f, "the dotnet runtime is owned by another thread"),
+            Self::ManagedHandlesNotInstalled =>
+                write!(f, "managed handles used before install_managed_handles"),
+            Self::CloneHandleFailed =>
+                write!(f, "CloneHandle returned a null handle, the target was already collected"),
+            Self::UnresolvedBindings { bindings, type_name, missing } => {
+                write!(f, "{}: {} method(s) missing on {}", bindings, missing.len(), type_name)?;
+                for e in missing {
+                    write!(f, "\n  {}", e)?;
+                }
+                Ok(())
+            },
+            Self::ManagedException { method, message, stack_trace } => {
+                write!(f, "{} threw {}", method, message)?;
+                if !stack_trace.is_empty() {
+                    write!(f, "\n{}", stack_trace)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl DotnetError {
+    /// Messages hostfxr reported through its error writer while the failing call ran
+    pub fn diagnostics(&self) -> &[String] {
+        match self {
+            Self::ResolveFrameworksFailed { diagnostics, .. }
+            | Self::MissingFrameworks { diagnostics, .. }
+            | Self::InitializeFailed { diagnostics, .. }
+            | Self::GetDelegateFailed { diagnostics, .. } => diagnostics,
+            _ => &[],
+        }
+    }
+}
+
+impl std::error::Error for DotnetError {}
+
+fn write_diagnostics(f: &mut fmt::Formatter<'_>, diagnostics: &[String]) -> fmt::Result {
+    for line in diagnostics {
+        write!(f, "\n  hostfxr: {}", line)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use std::{fmt, os::raw::c_void};
+
+use libloading::{Library, Symbol};
+
+use crate::dotnet::{diagnostics, hostfxr, DotnetError, HostfxrStatus};
+use crate::ffi_char_t::{char_t_ptr_to_string, CharTString};
+
+
+#[derive(Clone, Debug)]
+pub struct FrameworkResolution {
+    pub name: String,
+    pub requested_version: String,
+    pub resolved_version: Option<String>,
+    pub resolved_path: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FrameworkResolutionReport {
+    pub resolved: Vec<FrameworkResolution>,
+    pub unresolved: Vec<FrameworkResolution>,
+}
+
+impl FrameworkResolutionReport {
+    pub fn is_complete(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+}
+
+/// Resolves the frameworks a runtimeconfig asks for without initializing anything
+///
+/// Returns `Ok(None)` when hostfxr predates `hostfxr_resolve_frameworks_for_runtime_config` (.NET 9),
+/// and `DotnetError::MissingFrameworks` when any framework can't be resolved.
+pub fn resolve_frameworks(
+    hostfxr_lib: &Library,
+    runtimeconfig_path: &str
+) -> Result<Option<FrameworkResolutionReport>, DotnetError> {
+    unsafe {
+        let resolve: Symbol<hostfxr::hostfxr_resolve_frameworks_for_runtime_config_fn> =
+            match hostfxr_lib.get(b"hostfxr_resolve_frameworks_for_runtime_config") {
+                Ok(resolve) => resolve,
+                Err(_) => return Ok(None),
+            };
+
+        let config_path = CharTString::new(runtimeconfig_path);
+        let mut report: Option<FrameworkResolutionReport> = None;
+        let rc = resolve(
+            config_path.as_ptr(),
+            std::ptr::null(),
+            collect_resolution_result,
+            &mut report as *mut Option<FrameworkResolutionReport> as *mut c_void,
+        );
+
+        match report {
+            Some(report) if !report.is_complete() => Err(DotnetError::MissingFrameworks {
+                runtimeconfig_path: runtimeconfig_path.to_owned(),
+                unresolved: report.unresolved,
+                diagnostics: diagnostics::take_hostfxr_diagnostics(),
+            }),
+            Some(report) if HostfxrStatus::check(rc).is_ok() => Ok(Some(report)),
+            _ => Err(DotnetError::ResolveFrameworksFailed {
+                runtimeconfig_path: runtimeconfig_path.to_owned(),
+                status: HostfxrStatus::from_code(rc),
+                diagnostics: diagnostics::take_hostfxr_diagnostics(),
+            }),
+        }
+    }
+}
+
+unsafe fn collect_frameworks(frameworks: *const hostfxr::HostfxrFrameworkResult, count: usize) -> Vec<FrameworkResolution> {
+    if frameworks.is_null() {
+        return Vec::new();
+    }
+
+    std::slice::from_raw_parts(frameworks, count)
+        .iter()
+        .map(|framework| FrameworkResolution {
+            name: char_t_ptr_to_string(framework.name).unwrap_or_default(),
+            requested_version: char_t_ptr_to_string(framework.requested_version).unwrap_or_default(),
+            resolved_version: char_t_ptr_to_string(framework.resolved_version),
+            resolved_path: char_t_ptr_to_string(framework.resolved_path),
+        })
+        .collect()
+}
+
+// the result is only valid for the duration of the callback
+unsafe extern "C" fn collect_resolution_result(
+    result: *const hostfxr::HostfxrResolveFrameworksResult,
+    result_context: *mut c_void,
+) {
+    if result.is_null() || result_context.is_null() {
+        return;
+    }
+    let result = &*result;
+    let out = &mut *(result_context as *mut Option<FrameworkResolutionReport>);
+
+    *out = Some(FrameworkResolutionReport {
+        resolved: collect_frameworks(result.resolved_frameworks, result.resolved_count),
+        unresolved: collect_frameworks(result.unresolved_frameworks, result.unresolved_count),
+    });
+}
+
+impl fmt::Display for FrameworkResolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.resolved_version, &self.resolved_path) {
+            (Some(version), Some(path)) =>
+                write!(f, "{} {} -> {} [{}]", self.name, self.requested_version, version, path),
+            _ => write!(f, "{} {} -> not found", self.name, self.requested_version),
+        }
+    }
+}
+
+impl fmt::Display for FrameworkResolutionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Resolved frameworks ({}):", self.resolved.len())?;
+        for framework in &self.resolved {
+            writeln!(f, "  {}", framework)?;
+        }
+
+        writeln!(f, "Unresolved frameworks ({}):", self.unresolved.len())?;
+        for framework in &self.unresolved {
+            writeln!(f, "  {}", framework)?;
+        }
+
+        Ok(())
+    }
+}
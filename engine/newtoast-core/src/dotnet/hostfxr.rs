@@ -1,11 +1,9 @@
 
 use std::os::raw::{c_int, c_void};
 
-#[cfg(windows)]
-pub type char_t = u16;
-#[cfg(not(windows))]
-pub type char_t = u8;
+pub use crate::ffi_char_t::char_t;
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum HostfxrDelegate {
 	ComActivation = 0,
 	LoadInMemoryAssembly,
@@ -63,7 +61,9 @@ pub type hostfxr_error_writer_fn = unsafe extern "C" fn(message: *const char_t);
 // will be propagated to hostpolicy for the duration of the call. This means that errors from
 // both hostfxr and hostpolicy will be reporter through the same error writer.
 //
-pub type hostfxr_set_error_writer_fn = unsafe extern "C" fn(error_writer: hostfxr_error_writer_fn) -> hostfxr_error_writer_fn;
+pub type hostfxr_set_error_writer_fn = unsafe extern "C" fn(
+	error_writer: Option<hostfxr_error_writer_fn>, // nullable
+) -> Option<hostfxr_error_writer_fn>;
 
 pub type hostfxr_handle = *mut c_void;
 
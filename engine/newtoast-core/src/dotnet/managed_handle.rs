@@ -0,0 +1,105 @@
+use std::{fmt, os::raw::c_void, ptr::NonNull, sync::{atomic::{AtomicBool, Ordering}, PoisonError, RwLock, RwLockReadGuard}};
+
+use crate::dotnet::{DotnetContext, DotnetError, DotnetFunctionPtr};
+
+
+struct HandleFunctions {
+    free: DotnetFunctionPtr,
+    clone: DotnetFunctionPtr,
+}
+
+// handles are created and dropped all over the engine, threading the context through would
+// reach into every struct that can own one
+static HANDLE_FUNCTIONS: RwLock<Option<HandleFunctions>> = RwLock::new(None);
+
+// handles dropped after teardown are expected, say so once rather than per handle
+static REPORTED_LEAK: AtomicBool = AtomicBool::new(false);
+
+// drops run inside FFI callbacks, where a panic aborts, so a poisoned lock is used as is
+fn handle_functions() -> RwLockReadGuard<'static, Option<HandleFunctions>> {
+    HANDLE_FUNCTIONS.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Binds the managed side of `ManagedHandle`
+///
+/// `type_name` needs `[UnmanagedCallersOnly]` statics `FreeHandle(nint)` and
+/// `CloneHandle(nint) -> nint`. Handles dropped before this ran, or after the context
+/// closed, are leaked.
+pub fn install_managed_handles(dotnet: &DotnetContext, type_name: &str) -> Result<(), DotnetError> {
+    let functions = HandleFunctions {
+        free: dotnet.get_unmanaged_fn_pointer(type_name, "FreeHandle")?,
+        clone: dotnet.get_unmanaged_fn_pointer(type_name, "CloneHandle")?,
+    };
+    *HANDLE_FUNCTIONS.write().unwrap_or_else(PoisonError::into_inner) = Some(functions);
+    Ok(())
+}
+
+/// An owned `GCHandle` to a managed object
+///
+/// Managed code allocates the handle (`GCHandle.ToIntPtr(GCHandle.Alloc(obj))`) and hands it
+/// over, after which the engine owns it and frees it on drop. `as_raw` lends it back to managed
+/// callbacks so they can find the object again.
+///
+/// While a handle exists its target stays alive, which also keeps a collectible load context
+/// from unloading. Anything a plugin handed over has to be dropped before that plugin reloads.
+///
+/// `Option<ManagedHandle>` has the same layout as a nullable `nint`.
+#[repr(transparent)]
+pub struct ManagedHandle {
+    handle: NonNull<c_void>,
+}
+
+// GCHandle operations are thread-safe and the handle itself is an opaque token
+unsafe impl Send for ManagedHandle {}
+unsafe impl Sync for ManagedHandle {}
+
+impl ManagedHandle {
+    /// Takes ownership of a `GCHandle`, `None` for a null handle
+    ///
+    /// Safety: `raw` must be a live `GCHandle` that nothing else frees.
+    pub unsafe fn from_raw(raw: *mut c_void) -> Option<Self> {
+        NonNull::new(raw).map(|handle| Self { handle })
+    }
+
+    /// Lends the handle out, it stays owned by this `ManagedHandle`
+    pub fn as_raw(&self) -> *mut c_void {
+        self.handle.as_ptr()
+    }
+
+    /// Gives up ownership, whoever receives the handle has to free it
+    pub fn into_raw(self) -> *mut c_void {
+        let raw = self.as_raw();
+        std::mem::forget(self);
+        raw
+    }
+
+    /// Allocates a second `GCHandle` to the same object
+    ///
+    /// Not `Clone`, this fails once the context is closed and callers have to handle that.
+    pub fn try_clone(&self) -> Result<Self, DotnetError> {
+        let functions = handle_functions();
+        let functions = functions.as_ref().ok_or(DotnetError::ManagedHandlesNotInstalled)?;
+        let raw = unsafe {
+            functions.clone.try_call::<fn(*mut c_void) -> *mut c_void>(self.as_raw())?
+        };
+        unsafe { Self::from_raw(raw) }.ok_or(DotnetError::CloneHandleFailed)
+    }
+}
+
+impl Drop for ManagedHandle {
+    fn drop(&mut self) {
+        let functions = handle_functions();
+        let freed = functions.as_ref().map(|functions| unsafe {
+            functions.free.try_call::<fn(*mut c_void)>(self.as_raw())
+        });
+        if !matches!(freed, Some(Ok(()))) && !REPORTED_LEAK.swap(true, Ordering::Relaxed) {
+            println!("Leaking managed handles dropped while the runtime isn't bound");
+        }
+    }
+}
+
+impl fmt::Debug for ManagedHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ManagedHandle({:p})", self.handle)
+    }
+}
@@ -1,9 +1,37 @@
+mod bindings;
+pub(crate) use bindings::managed_bindings;
+
 pub mod coreclr_delegates;
 
+mod diagnostics;
+pub use diagnostics::take_hostfxr_diagnostics;
+
 mod dotnet;
 pub use dotnet::*;
 
+mod error;
+pub use error::*;
+
+pub mod embedded_assemblies;
+
+mod environment_info;
+pub use environment_info::*;
+
+mod framework_resolution;
+pub use framework_resolution::*;
+
 pub mod hostfxr;
+
+mod managed_handle;
+pub use managed_handle::*;
+
+mod runtime_config;
+pub use runtime_config::*;
+
+mod runtime_properties;
+pub use runtime_properties::*;
+
 pub mod nethost;
 
-pub mod string_name;
\ No newline at end of file
+mod threading;
+pub use threading::*;
\ No newline at end of file
@@ -25,7 +25,8 @@ pub struct GetHostfxrParameters {
     pub dotnet_root: *const char_t,
 }
 
-#[cfg_attr(windows, link(name = "nethost"))]
+// nethost.dll on Windows, libnethost.so on Linux
+#[link(name = "nethost")]
 extern "C" {
     /// Get the path to the hostfxr library
     ///
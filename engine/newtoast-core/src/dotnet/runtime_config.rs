@@ -0,0 +1,192 @@
+use std::{io::Write, path::{Path, PathBuf}};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::dotnet::DotnetError;
+
+
+/// How hostfxr may pick a framework version other than the one requested
+///
+/// Deserializes from snake_case like the rest of the manifest, `as_hostfxr_str` is the
+/// `rollForward` spelling hostfxr understands, see
+/// https://learn.microsoft.com/dotnet/core/versions/selection#control-roll-forward-behavior
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RollForward {
+    Disable,
+    LatestPatch,
+    #[default]
+    Minor,
+    LatestMinor,
+    Major,
+    LatestMajor,
+}
+
+impl RollForward {
+    pub fn as_hostfxr_str(self) -> &'static str {
+        match self {
+            Self::Disable => "Disable",
+            Self::LatestPatch => "LatestPatch",
+            Self::Minor => "Minor",
+            Self::LatestMinor => "LatestMinor",
+            Self::Major => "Major",
+            Self::LatestMajor => "LatestMajor",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameworkReference {
+    pub name: String,
+    pub version: String,
+}
+
+/// The contents of a runtimeconfig.json, built in code instead of read from disk
+///
+/// hostfxr only initializes from a file, so `write_temp` puts one in the temp directory
+/// for the duration of `create_context_with_config`.
+#[derive(Clone, Debug, Default)]
+pub struct RuntimeConfig {
+    pub frameworks: Vec<FrameworkReference>,
+    pub roll_forward: Option<RollForward>,
+    /// Written to `configProperties`, in order
+    pub properties: Vec<(String, String)>,
+}
+
+impl RuntimeConfig {
+    pub fn new<N: Into<String>, V: Into<String>>(framework_name: N, framework_version: V) -> Self {
+        Self {
+            frameworks: vec![FrameworkReference {
+                name: framework_name.into(),
+                version: framework_version.into(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    pub fn roll_forward(&mut self, roll_forward: RollForward) -> &mut Self {
+        self.roll_forward = Some(roll_forward);
+        self
+    }
+
+    pub fn property<N: Into<String>, V: Into<String>>(&mut self, name: N, value: V) -> &mut Self {
+        let name = name.into();
+        self.properties.retain(|(existing, _)| *existing != name);
+        self.properties.push((name, value.into()));
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut options = Map::new();
+        // a single framework uses the singular key, which is what every SDK-generated file does
+        match self.frameworks.as_slice() {
+            [framework] => { options.insert("framework".into(), json!(framework)); },
+            frameworks => { options.insert("frameworks".into(), json!(frameworks)); },
+        }
+        if let Some(roll_forward) = self.roll_forward {
+            options.insert("rollForward".into(), json!(roll_forward.as_hostfxr_str()));
+        }
+        let properties: Map<String, Value> = self.properties.iter()
+            .map(|(name, value)| (name.clone(), Value::String(value.clone())))
+            .collect();
+        options.insert("configProperties".into(), Value::Object(properties));
+
+        serde_json::to_string_pretty(&json!({ "runtimeOptions": options }))
+            .expect("a runtimeconfig always serializes")
+    }
+
+    /// Writes the config to a uniquely named file that is deleted when the guard drops
+    pub fn write_temp(&self) -> Result<TempRuntimeConfig, DotnetError> {
+        let unique = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos())
+            .unwrap_or_default();
+        let path = std::env::temp_dir()
+            .join(format!("newtoast-{}-{}.runtimeconfig.json", std::process::id(), unique));
+
+        let written = std::fs::File::create(&path)
+            .and_then(|mut file| file.write_all(self.to_json().as_bytes()));
+        match written {
+            Ok(()) => Ok(TempRuntimeConfig { path }),
+            Err(e) => {
+                let _ = std::fs::remove_file(&path);
+                Err(DotnetError::WriteRuntimeConfigFailed {
+                    path: path.to_string_lossy().into_owned(),
+                    message: e.to_string(),
+                })
+            },
+        }
+    }
+}
+
+/// A runtimeconfig written by `RuntimeConfig::write_temp`, removed on drop
+pub struct TempRuntimeConfig {
+    path: PathBuf,
+}
+
+impl TempRuntimeConfig {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempRuntimeConfig {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runtime_options(config: &RuntimeConfig) -> Value {
+        let json: Value = serde_json::from_str(&config.to_json()).unwrap();
+        json["runtimeOptions"].clone()
+    }
+
+    #[test]
+    fn single_framework_uses_the_singular_key() {
+        let options = runtime_options(&RuntimeConfig::new("Microsoft.NETCore.App", "9.0.0"));
+
+        assert_eq!(options["framework"], json!({ "name": "Microsoft.NETCore.App", "version": "9.0.0" }));
+        assert!(options.get("frameworks").is_none());
+        assert!(options.get("rollForward").is_none());
+    }
+
+    #[test]
+    fn several_frameworks_use_the_plural_key() {
+        let mut config = RuntimeConfig::new("Microsoft.NETCore.App", "9.0.0");
+        config.frameworks.push(FrameworkReference { name: "Microsoft.AspNetCore.App".into(), version: "9.0.0".into() });
+        let options = runtime_options(&config);
+
+        assert!(options.get("framework").is_none());
+        assert_eq!(options["frameworks"].as_array().map(Vec::len), Some(2));
+        assert_eq!(options["frameworks"][1]["name"], "Microsoft.AspNetCore.App");
+    }
+
+    #[test]
+    fn roll_forward_uses_hostfxr_spelling() {
+        let mut config = RuntimeConfig::new("Microsoft.NETCore.App", "9.0.0");
+        config.roll_forward(RollForward::LatestMinor);
+
+        assert_eq!(runtime_options(&config)["rollForward"], "LatestMinor");
+    }
+
+    #[test]
+    fn properties_are_strings_and_later_values_win() {
+        let mut config = RuntimeConfig::new("Microsoft.NETCore.App", "9.0.0");
+        config.property("System.GC.Server", "false");
+        config.property("System.Globalization.Invariant", "true");
+        config.property("System.GC.Server", "true");
+        let options = runtime_options(&config);
+
+        assert_eq!(options["configProperties"], json!({
+            "System.GC.Server": "true",
+            "System.Globalization.Invariant": "true",
+        }));
+        assert_eq!(config.properties[1].0, "System.GC.Server");
+    }
+}
@@ -0,0 +1,104 @@
+use std::path::Path;
+
+
+pub const PROPERTY_GC_SERVER: &str = "System.GC.Server";
+pub const PROPERTY_GC_CONCURRENT: &str = "System.GC.Concurrent";
+pub const PROPERTY_GC_RETAIN_VM: &str = "System.GC.RetainVM";
+pub const PROPERTY_PROBING_DIRECTORIES: &str = "PROBING_DIRECTORIES";
+pub const PROPERTY_NATIVE_DLL_SEARCH_DIRECTORIES: &str = "NATIVE_DLL_SEARCH_DIRECTORIES";
+pub const PROPERTY_APP_CONTEXT_BASE_DIRECTORY: &str = "APP_CONTEXT_BASE_DIRECTORY";
+
+
+/// Runtime properties applied to the host context after it is initialized and before the runtime starts
+///
+/// Anything set here overrides `configProperties` from the runtimeconfig. Properties are applied
+/// in insertion order; setting the same name twice keeps the later value.
+#[derive(Clone, Debug, Default)]
+pub struct RuntimeProperties {
+    properties: Vec<(String, Option<String>)>,
+}
+
+impl RuntimeProperties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set<N: Into<String>, V: Into<String>>(&mut self, name: N, value: V) -> &mut Self {
+        self.insert(name.into(), Some(value.into()))
+    }
+
+    /// Removes a property the runtimeconfig would otherwise provide
+    pub fn remove<N: Into<String>>(&mut self, name: N) -> &mut Self {
+        self.insert(name.into(), None)
+    }
+
+    pub fn gc_server(&mut self, enabled: bool) -> &mut Self {
+        self.set(PROPERTY_GC_SERVER, bool_str(enabled))
+    }
+
+    pub fn gc_concurrent(&mut self, enabled: bool) -> &mut Self {
+        self.set(PROPERTY_GC_CONCURRENT, bool_str(enabled))
+    }
+
+    pub fn gc_retain_vm(&mut self, enabled: bool) -> &mut Self {
+        self.set(PROPERTY_GC_RETAIN_VM, bool_str(enabled))
+    }
+
+    /// Sets an `AppContext` switch, e.g. `System.Globalization.Invariant`
+    pub fn app_context_switch<N: Into<String>>(&mut self, name: N, enabled: bool) -> &mut Self {
+        self.set(name, bool_str(enabled))
+    }
+
+    pub fn probing_directories<I, P>(&mut self, directories: I) -> &mut Self
+        where I: IntoIterator<Item = P>, P: AsRef<Path>
+    {
+        let joined = join_paths(directories);
+        self.set(PROPERTY_PROBING_DIRECTORIES, joined)
+    }
+
+    pub fn native_dll_search_directories<I, P>(&mut self, directories: I) -> &mut Self
+        where I: IntoIterator<Item = P>, P: AsRef<Path>
+    {
+        let joined = join_paths(directories);
+        self.set(PROPERTY_NATIVE_DLL_SEARCH_DIRECTORIES, joined)
+    }
+
+    /// What `AppContext.BaseDirectory` reports, defaults to the runtimeconfig's directory
+    pub fn app_context_base_directory<P: AsRef<Path>>(&mut self, directory: P) -> &mut Self {
+        // the runtime expects a trailing separator here
+        let mut directory = directory.as_ref().to_string_lossy().into_owned();
+        if !directory.ends_with(std::path::MAIN_SEPARATOR) {
+            directory.push(std::path::MAIN_SEPARATOR);
+        }
+        self.set(PROPERTY_APP_CONTEXT_BASE_DIRECTORY, directory)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.properties.iter().map(|(name, value)| (name.as_str(), value.as_deref()))
+    }
+
+    fn insert(&mut self, name: String, value: Option<String>) -> &mut Self {
+        self.properties.retain(|(existing, _)| *existing != name);
+        self.properties.push((name, value));
+        self
+    }
+}
+
+fn bool_str(value: bool) -> &'static str {
+    if value { "true" } else { "false" }
+}
+
+// the runtime splits path-list properties on the platform's PATH separator
+fn join_paths<I, P>(directories: I) -> String
+    where I: IntoIterator<Item = P>, P: AsRef<Path>
+{
+    let separator = if cfg!(windows) { ";" } else { ":" };
+    directories.into_iter()
+        .map(|dir| dir.as_ref().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
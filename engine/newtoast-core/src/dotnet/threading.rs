@@ -0,0 +1,73 @@
+use std::{marker::PhantomData, sync::{Mutex, MutexGuard, PoisonError}, thread::{self, ThreadId}};
+
+use crate::dotnet::DotnetError;
+
+
+// Managed code is entered through [UnmanagedCallersOnly] function pointers only. Their
+// reverse P/Invoke stubs attach whatever native thread calls them to the runtime on first use,
+// and the runtime detaches it again when that thread exits, so worker threads need no explicit
+// attach/detach. What isn't free is thread affinity on the managed side: the engine lifecycle,
+// the render server binding and the game load context all assume one thread, and that is the
+// thread that created the DotnetContext. The claim lasts as long as the contexts created on that
+// thread, so once they're all dropped another thread can create one (tests run on fresh threads).
+// The count is for contexts created side by side on the same thread.
+static MAIN_THREAD: Mutex<Option<(ThreadId, usize)>> = Mutex::new(None);
+
+fn main_thread() -> MutexGuard<'static, Option<(ThreadId, usize)>> {
+    // nothing panics while holding the lock, but don't let a poisoned lock take the engine down
+    MAIN_THREAD.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Held by a DotnetContext, the current thread stays the main thread until every claim is dropped
+#[derive(Debug)]
+pub(super) struct MainThreadClaim {
+    _not_send: PhantomData<*const ()>,
+}
+
+/// Records the current thread as the main thread, fails if another thread currently is
+pub(super) fn claim_main_thread() -> Result<MainThreadClaim, DotnetError> {
+    let current = thread::current().id();
+    let mut main = main_thread();
+    match &mut *main {
+        Some((thread, claims)) if *thread == current => *claims += 1,
+        Some(_) => return Err(DotnetError::NotMainThread),
+        None => *main = Some((current, 1)),
+    }
+    Ok(MainThreadClaim { _not_send: PhantomData })
+}
+
+impl Drop for MainThreadClaim {
+    fn drop(&mut self) {
+        let mut main = main_thread();
+        if let Some((_, claims)) = &mut *main {
+            *claims -= 1;
+            if *claims == 0 {
+                *main = None;
+            }
+        }
+    }
+}
+
+pub fn is_main_thread() -> bool {
+    main_thread().is_some_and(|(main, _)| main == thread::current().id())
+}
+
+/// Proof that the caller is on the thread that created the DotnetContext
+///
+/// Bindings marked `@main_thread` in `managed_bindings!` take one of these. It is `!Send` and
+/// `!Sync`, so a worker thread holding the bindings can still only reach the rest of them.
+#[derive(Copy, Clone, Debug)]
+pub struct MainThread {
+    _not_send: PhantomData<*const ()>,
+}
+
+impl MainThread {
+    /// `None` off the main thread, or while no DotnetContext exists
+    pub fn current() -> Option<Self> {
+        if is_main_thread() {
+            Some(Self { _not_send: PhantomData })
+        } else {
+            None
+        }
+    }
+}
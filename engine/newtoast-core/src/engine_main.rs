@@ -1,39 +1,105 @@
-use std::{ffi::{CStr, CString}};
+use std::ffi::{CStr, CString};
+#[cfg(feature = "dotnet")]
+use std::time::Duration;
 use sdl3_sys::{error::SDL_GetError, events::{SDL_EventType, SDL_EVENT_QUIT}, init::{SDL_Init, SDL_INIT_VIDEO}};
 
-use crate::{dotnet::{self, DotnetContext}, render::{RenderContext, RenderServer}, runtime_interface::entrypoint::RuntimeEntrypoints, util::timing::InterpolatingTimer};
+use crate::{game::Game, render::{RenderContext, RenderServer}, runtime_interface::config::Config, util::timing::InterpolatingTimer};
+#[cfg(feature = "dotnet")]
+use crate::{dotnet::{self, embedded_assemblies, DotnetContext, DotnetError, MainThread, RuntimeProperties}, runtime_interface::{entrypoint::RuntimeEntrypoints, managed_game::ManagedGame, native_api::NATIVE_API, plugin::{discover_mods, PluginHost}}, manifest::ProjectManifest};
 
 
-fn setup_dotnet_runtime(dotnet: &mut DotnetContext) -> Result<RuntimeEntrypoints, &'static str> {
-    let mut fpath = std::env::current_dir().unwrap();
-    fpath.push("build/framework/NT.dll");
+#[cfg(feature = "dotnet")]
+const HOT_RELOAD_SETTLE_TIME: Duration = Duration::from_millis(500);
 
-    dotnet.load_assembly(fpath.to_str().unwrap());
 
-        RuntimeEntrypoints::find_entrypoint_and_build(dotnet)
+#[cfg(feature = "dotnet")]
+fn setup_dotnet_runtime(dotnet: &mut DotnetContext, main_thread: &MainThread, manifest: &ProjectManifest) -> Result<RuntimeEntrypoints, DotnetError> {
+    if embedded_assemblies::has_embedded_assemblies() {
+        for embedded in embedded_assemblies::EMBEDDED_ASSEMBLIES {
+            println!("Loading embedded assembly {}", embedded.name);
+            dotnet.load_assembly_from_bytes(embedded.assembly, embedded.symbols)?;
+        }
+    }
+    else {
+        for assembly in &manifest.runtime.assemblies {
+            dotnet.load_assembly(&assembly.to_string_lossy())?;
+        }
+    }
+
+    dotnet::install_managed_handles(dotnet, "NTF.ManagedHandleInternal, NT")?;
+    let runtime_interface = RuntimeEntrypoints::resolve(dotnet)?;
+    // plugins may touch native functions from static constructors, so this goes before any load
+    runtime_interface.bind_native_api(main_thread, &NATIVE_API)?;
+    Ok(runtime_interface)
 }
 
-pub fn engine_main() {
-    println!("PWD:\n  {}", std::env::current_dir().unwrap().display());
+/// Hosts .NET as described by the manifest and runs the game and its mods as a `ManagedGame`
+#[cfg(feature = "dotnet")]
+pub fn engine_main(manifest: &ProjectManifest) -> Result<(), DotnetError> {
+    println!("Manifest:\n  {}", manifest.path.display());
+
+    let config = manifest.engine_config();
+
+    let hostfxr_lib = dotnet::load_hostfxr()?;
+    let mut dotnet = match &manifest.runtime.config {
+        Some(runtimeconfig_path) => dotnet::create_context(
+            &hostfxr_lib,
+            &runtimeconfig_path.to_string_lossy(),
+            &manifest.runtime_properties(),
+        )?,
+        None => dotnet::create_context_with_config(
+            &hostfxr_lib,
+            &manifest.runtime_config(),
+            manifest.base_directory(),
+            &RuntimeProperties::new(),
+        )?,
+    };
+
+    // create_context claimed this thread, managed lifecycle calls stay on it from here on
+    let main_thread = MainThread::current().expect("create_context claims the main thread");
+    let runtime_interface = setup_dotnet_runtime(&mut dotnet, &main_thread, manifest)?;
+    // test_dotnet(&mut dotnet);
 
-    let hostfxr_lib = dotnet::load_hostfxr();
-    let mut dotnet = dotnet::create_context(&hostfxr_lib, "runtimeconfig.json");
+    let mut plugins = PluginHost::new(config.managed_error_policy);
+
+    // embedded builds are shipped builds, loose files next to them are never picked up
+    let loose_files = !embedded_assemblies::has_embedded_assemblies();
+    let hot_reload = Some(HOT_RELOAD_SETTLE_TIME).filter(|_| manifest.game.hot_reload);
+    let entry_class = manifest.game.entry_class.as_deref();
+    match manifest.game.assembly.as_deref().filter(|_| loose_files) {
+        // a game that fails to build or load can still be fixed and picked up by hot reload
+        Some(game_assembly) => {
+            let loaded = plugins.load(&main_thread, &runtime_interface, "game", Some(game_assembly), entry_class, hot_reload);
+            if hot_reload.is_none() {
+                loaded?;
+            }
+        },
+        None =>
+            plugins.load(&main_thread, &runtime_interface, "game", None, entry_class, None)?,
+    }
 
-    let runtime_interface = setup_dotnet_runtime(&mut dotnet)
-        .expect("Failed to craete RuntimeEntrypoints");
-    // test_dotnet(&mut dotnet);
+    // a broken mod only takes itself out, the game still has to load
+    if let Some(mods_directory) = config.mods_directory.as_deref().filter(|_| loose_files) {
+        for (name, assembly) in discover_mods(mods_directory) {
+            let _ = plugins.load(&main_thread, &runtime_interface, &name, Some(&assembly), None, hot_reload);
+        }
+    }
 
-    core_loop(&runtime_interface);
+    let mut game = ManagedGame::new(main_thread, runtime_interface, plugins);
+    run_game(&config, &mut game)
 }
 
-// lifetime specifiers probably aren't necessary but being explicit might help avoid pits
-fn core_loop(runtime_interface: &RuntimeEntrypoints) {
+/// Opens the window and runs `game` until it's closed
+///
+/// This is the whole engine loop. Rust games, tools and integration tests call it directly,
+/// without the `dotnet` feature nothing else is needed.
+pub fn run_game<G: Game>(config: &Config, game: &mut G) -> Result<(), G::Error> {
     unsafe {
         // HACK this is extraordinarily cursed
         // but once it's chugging along I can start to make kinder interfaces
 
         // Initialize runtime here to pick up any config changes
-        let init_config = runtime_interface.setup_and_get_config();
+        let init_config = game.init_config()?;
 
         let initflags = SDL_INIT_VIDEO;
         let init_result = SDL_Init(initflags);
@@ -43,16 +109,16 @@ fn core_loop(runtime_interface: &RuntimeEntrypoints) {
         }
 
         let mut render_context = RenderContext::new(&init_config).expect("RenderContext::new failed");
-        let mut render_server = RenderServer::new();
+        let mut render_server = RenderServer::new(render_context.gl().clone());
 
 
         // TODO initialize subsystems
 
-        runtime_interface.initialize();
+        game.initialize(&mut render_server);
 
         let mut tick_events = Vec::new();
         let mut should_exit = false;
-        let mut timer = InterpolatingTimer::new(1.0 / 60.0);
+        let mut timer = InterpolatingTimer::new(config.fixed_timestep);
 
         'gameloop: loop {
             // Handle time
@@ -70,27 +136,31 @@ fn core_loop(runtime_interface: &RuntimeEntrypoints) {
             }
             tick_events.clear();
 
-            runtime_interface.bind_render_server(&mut render_server);
+            game.begin_frame(&mut render_server);
+
             // TODO revisit the idea of passing dt like this at all
             for _ in 0..updates {
-                runtime_interface.update(1.0 / 60.0);
+                game.update(&mut render_server, config.fixed_timestep);
             }
 
             // TODO figure out how to properly use dt
-            runtime_interface.draw();
-            runtime_interface.unbind_render_server();
+            game.draw(&mut render_server);
+            game.end_frame(&mut render_server);
 
             render_context.imgui_frame(|ui| {
                 ui.show_demo_window(&mut true);
+                game.debug_ui(ui);
             });
 
-            render_context.render_frame();
+            render_context.render_frame(&render_server);
 
             if should_exit {
-                runtime_interface.shutdown();
+                game.shutdown();
                 break 'gameloop;
             }
         }
 
     }
-}
\ No newline at end of file
+
+    Ok(())
+}
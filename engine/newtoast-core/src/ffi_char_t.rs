@@ -1,5 +1,88 @@
+use std::ffi::OsStr;
 
+// hostfxr/nethost use pal::char_t, which is wchar_t (UTF-16) on Windows and char (UTF-8) elsewhere
 #[cfg(windows)]
 pub type char_t = u16;
 #[cfg(not(windows))]
-pub type char_t = u8;
\ No newline at end of file
+pub type char_t = u8;
+
+
+/// Owned, null-terminated string in the host's native `char_t` encoding
+#[derive(Clone, Debug)]
+pub struct CharTString {
+    buf: Vec<char_t>,
+}
+
+impl CharTString {
+    pub fn new<S: AsRef<OsStr> + ?Sized>(s: &S) -> Self {
+        let mut buf = encode_os_str(s.as_ref());
+        // interior nulls would silently truncate the string on the native side
+        if let Some(nul) = buf.iter().position(|&c| c == 0) {
+            buf.truncate(nul);
+        }
+        buf.push(0);
+        Self { buf }
+    }
+
+    pub fn as_ptr(&self) -> *const char_t {
+        self.buf.as_ptr()
+    }
+
+    /// Length in `char_t` units, not counting the null terminator
+    pub fn len(&self) -> usize {
+        self.buf.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl From<&str> for CharTString {
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+
+#[cfg(windows)]
+fn encode_os_str(s: &OsStr) -> Vec<char_t> {
+    use std::os::windows::ffi::OsStrExt;
+    s.encode_wide().collect()
+}
+
+#[cfg(not(windows))]
+fn encode_os_str(s: &OsStr) -> Vec<char_t> {
+    use std::os::unix::ffi::OsStrExt;
+    s.as_bytes().to_vec()
+}
+
+
+/// Decodes a `char_t` slice (without terminator), replacing invalid sequences
+#[cfg(windows)]
+pub fn char_t_slice_to_string(s: &[char_t]) -> String {
+    String::from_utf16_lossy(s)
+}
+
+/// Decodes a `char_t` slice (without terminator), replacing invalid sequences
+#[cfg(not(windows))]
+pub fn char_t_slice_to_string(s: &[char_t]) -> String {
+    String::from_utf8_lossy(s).into_owned()
+}
+
+/// Decodes a null-terminated `char_t` string, returns `None` for a null pointer
+///
+/// # Safety
+/// `ptr` must be null or point to a valid null-terminated `char_t` string
+pub unsafe fn char_t_ptr_to_string(ptr: *const char_t) -> Option<String> {
+    if ptr.is_null() {
+        return None;
+    }
+
+    let mut len = 0;
+    while *ptr.add(len) != 0 {
+        len += 1;
+    }
+
+    Some(char_t_slice_to_string(std::slice::from_raw_parts(ptr, len)))
+}
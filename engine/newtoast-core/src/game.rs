@@ -0,0 +1,52 @@
+use crate::{render::RenderServer, runtime_interface::config::InitConfig};
+
+
+/// What `run_game` drives, the same lifecycle as a managed `NTEntrypoint`
+///
+/// With the `dotnet` feature the managed plugins run as a `ManagedGame`, anything else
+/// implementing this is plain Rust and needs neither a .NET runtime nor NT.dll.
+///
+/// ```ignore
+/// struct Spinner { renderable: Id }
+///
+/// impl Game for Spinner {
+///     type Error = std::convert::Infallible;
+///
+///     fn initialize(&mut self, render_server: &mut RenderServer) {
+///         self.renderable = render_server.renderable_2d_create();
+///     }
+///     fn update(&mut self, _render_server: &mut RenderServer, _delta_time: f64) {}
+///     fn draw(&mut self, _render_server: &mut RenderServer) {}
+///     fn shutdown(&mut self) {}
+/// }
+///
+/// run_game(&Config::default(), &mut Spinner { renderable: 0 })?;
+/// ```
+pub trait Game {
+    /// Only `init_config` can fail, everything after it has to handle its own errors
+    type Error;
+
+    /// Window settings, asked for once before the window opens
+    fn init_config(&mut self) -> Result<InitConfig, Self::Error> {
+        Ok(InitConfig::default())
+    }
+
+    fn initialize(&mut self, render_server: &mut RenderServer);
+
+    /// Called zero or more times a frame, `delta_time` is always `Config::fixed_timestep`
+    fn update(&mut self, render_server: &mut RenderServer, delta_time: f64);
+
+    fn draw(&mut self, render_server: &mut RenderServer);
+
+    /// Called once when the window closes, the render server is torn down right after
+    fn shutdown(&mut self);
+
+    /// Once a frame before the first update
+    fn begin_frame(&mut self, _render_server: &mut RenderServer) {}
+
+    /// Once a frame after draw
+    fn end_frame(&mut self, _render_server: &mut RenderServer) {}
+
+    /// Inside the frame's imgui pass, for debug windows and overlays
+    fn debug_ui(&mut self, _ui: &imgui::Ui) {}
+}
@@ -1,13 +1,28 @@
 #![allow(unused_parens)]
 #![allow(non_camel_case_types)]
 
+#[cfg(feature = "dotnet")]
 mod dotnet;
 mod engine_main;
+mod game;
+mod manifest;
 mod render;
 mod runtime_interface;
 mod util;
 
+pub use engine_main::run_game;
+pub use game::Game;
+pub use manifest::{ManifestError, ProjectManifest};
+pub use render::{math_types::{Color, Transform2D}, Id, Mesh2DData, RenderServer};
+pub use runtime_interface::config::{Config, InitConfig, ManagedErrorPolicy};
+
+#[cfg(feature = "dotnet")]
+pub use dotnet::{DotnetEnvironmentInfo, DotnetError};
+#[cfg(feature = "dotnet")]
 pub use engine_main::engine_main;
+#[cfg(feature = "dotnet")]
+pub use runtime_interface::csharp_interop::{generate_csharp_interop, is_csharp_interop_current, write_csharp_interop};
 
 
+#[cfg(feature = "dotnet")]
 mod ffi_char_t;
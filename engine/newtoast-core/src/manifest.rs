@@ -0,0 +1,381 @@
+use std::{collections::BTreeMap, fmt, path::{Path, PathBuf}};
+
+use serde::Deserialize;
+
+#[cfg(feature = "dotnet")]
+use crate::dotnet::{RollForward, RuntimeConfig, RuntimeProperties};
+use crate::runtime_interface::config::{Config, ManagedErrorPolicy};
+
+
+pub const MANIFEST_FILE_NAME: &str = "newtoast.toml";
+
+/// A project's `newtoast.toml`
+///
+/// ```toml
+/// [runtime]
+/// assemblies = ["build/framework/NT.dll"]
+///
+/// [runtime.framework]
+/// name = "Microsoft.NETCore.App"
+/// version = "9.0.0"
+/// roll_forward = "latest_minor"
+///
+/// [runtime.properties]
+/// "System.GC.Server" = false
+///
+/// [game]
+/// assembly = "build/game/NTTest.dll"
+/// entry_class = "NTTest.Game"
+/// mods_directory = "build/mods"
+/// hot_reload = true
+///
+/// [engine]
+/// fixed_timestep = 0.016666
+/// managed_error_policy = "overlay"
+/// data_directories = ["assets"]
+/// ```
+///
+/// Every section and key is optional. Relative paths are resolved against the directory the
+/// manifest is in, so the executable can be launched from anywhere. The runtimeconfig is
+/// generated from `[runtime.framework]` and `[runtime.properties]` unless `runtime.config`
+/// points at an existing runtimeconfig.json. Without the `dotnet` feature `[runtime]` is
+/// skipped and the rest still applies to a Rust `Game`.
+#[derive(Clone, Debug)]
+pub struct ProjectManifest {
+    pub path: PathBuf,
+    #[cfg(feature = "dotnet")]
+    pub runtime: RuntimeSection,
+    pub game: GameSection,
+    pub engine: EngineSection,
+}
+
+#[cfg(feature = "dotnet")]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuntimeSection {
+    /// A runtimeconfig.json to use instead of `framework`, `properties` still override its values
+    pub config: Option<PathBuf>,
+    pub framework: FrameworkSection,
+    /// Values can be strings, booleans or numbers, they all reach the runtime as strings
+    pub properties: BTreeMap<String, toml::Value>,
+    /// Loaded into the default context in order, the first one has to be the framework
+    pub assemblies: Vec<PathBuf>,
+}
+
+#[cfg(feature = "dotnet")]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FrameworkSection {
+    pub name: String,
+    pub version: String,
+    /// snake_case, e.g. `latest_minor`
+    pub roll_forward: RollForward,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameSection {
+    /// Without one the entrypoint is looked up in `runtime.assemblies`
+    pub assembly: Option<PathBuf>,
+    /// Overrides the assembly's `NTEntrypointSpecifier`
+    pub entry_class: Option<String>,
+    pub mods_directory: Option<PathBuf>,
+    pub hot_reload: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EngineSection {
+    pub fixed_timestep: f64,
+    pub managed_error_policy: ManagedErrorPolicy,
+    pub data_directories: Vec<PathBuf>,
+}
+
+#[cfg(feature = "dotnet")]
+impl Default for RuntimeSection {
+    fn default() -> Self {
+        Self {
+            config: None,
+            framework: FrameworkSection::default(),
+            properties: BTreeMap::new(),
+            assemblies: vec!["build/framework/NT.dll".into()],
+        }
+    }
+}
+
+#[cfg(feature = "dotnet")]
+impl Default for FrameworkSection {
+    fn default() -> Self {
+        Self {
+            name: "Microsoft.NETCore.App".into(),
+            version: "9.0.0".into(),
+            roll_forward: RollForward::default(),
+        }
+    }
+}
+
+impl Default for GameSection {
+    fn default() -> Self {
+        Self {
+            assembly: None,
+            entry_class: None,
+            mods_directory: None,
+            hot_reload: true,
+        }
+    }
+}
+
+impl Default for EngineSection {
+    fn default() -> Self {
+        Self {
+            fixed_timestep: 1.0 / 60.0,
+            managed_error_policy: ManagedErrorPolicy::default(),
+            data_directories: Vec::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawManifest {
+    #[cfg(feature = "dotnet")]
+    #[serde(default)]
+    runtime: RuntimeSection,
+    // the same manifest works for both builds, without .NET there's just nothing to configure
+    #[cfg(not(feature = "dotnet"))]
+    #[serde(default)]
+    #[allow(dead_code)]
+    runtime: serde::de::IgnoredAny,
+    #[serde(default)]
+    game: GameSection,
+    #[serde(default)]
+    engine: EngineSection,
+}
+
+#[derive(Clone, Debug)]
+pub enum ManifestError {
+    NotFound {
+        searched: Vec<PathBuf>,
+    },
+    ReadFailed {
+        path: PathBuf,
+        message: String,
+    },
+    ParseFailed {
+        path: PathBuf,
+        message: String,
+    },
+    Invalid {
+        path: PathBuf,
+        message: String,
+    },
+}
+
+impl ProjectManifest {
+    /// Uses `explicit_path` if given, otherwise looks next to the executable and then in the
+    /// working directory
+    pub fn locate(explicit_path: Option<&Path>) -> Result<Self, ManifestError> {
+        if let Some(path) = explicit_path {
+            return Self::load(path);
+        }
+
+        let mut searched = Vec::new();
+        if let Some(exe_dir) = std::env::current_exe().ok().and_then(|exe| exe.parent().map(Path::to_path_buf)) {
+            searched.push(exe_dir.join(MANIFEST_FILE_NAME));
+        }
+        if let Ok(cwd) = std::env::current_dir() {
+            searched.push(cwd.join(MANIFEST_FILE_NAME));
+        }
+
+        match searched.iter().find(|path| path.is_file()) {
+            Some(path) => Self::load(path),
+            None => Err(ManifestError::NotFound { searched }),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| ManifestError::ReadFailed { path: path.to_owned(), message: e.to_string() })?;
+        Self::parse(path, &text)
+    }
+
+    /// Parses manifest text, `path` is only used to resolve relative paths and in errors
+    pub fn parse(path: &Path, text: &str) -> Result<Self, ManifestError> {
+        let raw: RawManifest = toml::from_str(text)
+            .map_err(|e| ManifestError::ParseFailed { path: path.to_owned(), message: e.to_string() })?;
+
+        let fixed_timestep = raw.engine.fixed_timestep;
+        if !fixed_timestep.is_finite() || fixed_timestep <= 0.0 {
+            return Err(ManifestError::Invalid {
+                path: path.to_owned(),
+                message: format!("engine.fixed_timestep must be positive and finite, got {}", fixed_timestep),
+            });
+        }
+
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.to_owned());
+        let base = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let resolve = |relative: &Path| base.join(relative);
+
+        Ok(Self {
+            #[cfg(feature = "dotnet")]
+            runtime: RuntimeSection {
+                config: raw.runtime.config.as_deref().map(resolve),
+                assemblies: raw.runtime.assemblies.iter().map(|p| resolve(p)).collect(),
+                ..raw.runtime
+            },
+            game: GameSection {
+                assembly: raw.game.assembly.as_deref().map(resolve),
+                mods_directory: raw.game.mods_directory.as_deref().map(resolve),
+                ..raw.game
+            },
+            engine: EngineSection {
+                data_directories: raw.engine.data_directories.iter().map(|p| resolve(p)).collect(),
+                ..raw.engine
+            },
+            path,
+        })
+    }
+
+    /// Directory the manifest is in, everything relative resolves against this
+    pub fn base_directory(&self) -> &Path {
+        self.path.parent().unwrap_or(Path::new(""))
+    }
+
+    /// The runtimeconfig described by `[runtime.framework]` and `[runtime.properties]`
+    #[cfg(feature = "dotnet")]
+    pub fn runtime_config(&self) -> RuntimeConfig {
+        let framework = &self.runtime.framework;
+        let mut config = RuntimeConfig::new(&framework.name, &framework.version);
+        config.roll_forward(framework.roll_forward);
+        for (name, value) in self.runtime_property_values() {
+            config.property(name, value);
+        }
+        config
+    }
+
+    /// `[runtime.properties]` as overrides, for when `runtime.config` is used
+    #[cfg(feature = "dotnet")]
+    pub fn runtime_properties(&self) -> RuntimeProperties {
+        let mut properties = RuntimeProperties::new();
+        for (name, value) in self.runtime_property_values() {
+            properties.set(name, value);
+        }
+        properties
+    }
+
+    #[cfg(feature = "dotnet")]
+    fn runtime_property_values(&self) -> impl Iterator<Item = (&str, String)> {
+        self.runtime.properties.iter().map(|(name, value)| {
+            let value = match value {
+                toml::Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            (name.as_str(), value)
+        })
+    }
+
+    pub fn engine_config(&self) -> Config {
+        Config {
+            managed_error_policy: self.engine.managed_error_policy,
+            mods_directory: self.game.mods_directory.clone(),
+            fixed_timestep: self.engine.fixed_timestep,
+            data_directories: self.engine.data_directories.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound { searched } => {
+                write!(f, "no {} found, pass one with --manifest. Searched:", MANIFEST_FILE_NAME)?;
+                for path in searched {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            },
+            Self::ReadFailed { path, message } =>
+                write!(f, "could not read manifest \"{}\": {}", path.display(), message),
+            Self::ParseFailed { path, message } =>
+                write!(f, "invalid manifest \"{}\": {}", path.display(), message),
+            Self::Invalid { path, message } =>
+                write!(f, "invalid manifest \"{}\": {}", path.display(), message),
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest_path() -> PathBuf {
+        std::env::temp_dir().join("project").join(MANIFEST_FILE_NAME)
+    }
+
+    #[test]
+    fn empty_manifest_is_defaults() {
+        let manifest = ProjectManifest::parse(&manifest_path(), "").unwrap();
+        let base = manifest_path().parent().unwrap().to_owned();
+
+        assert_eq!(manifest.path, manifest_path());
+        assert_eq!(manifest.base_directory(), base);
+        #[cfg(feature = "dotnet")]
+        assert_eq!(manifest.runtime.assemblies, vec![base.join("build/framework/NT.dll")]);
+        assert!(manifest.game.hot_reload);
+        assert_eq!(manifest.engine.fixed_timestep, 1.0 / 60.0);
+        assert_eq!(manifest.engine.managed_error_policy, ManagedErrorPolicy::default());
+    }
+
+    #[test]
+    fn relative_paths_resolve_against_the_manifest() {
+        let absolute = std::env::temp_dir().join("elsewhere").join("Game.dll");
+        let text = format!(
+            "[game]\nassembly = '{}'\nmods_directory = 'build/mods'\n\n[engine]\ndata_directories = ['assets']\n",
+            absolute.display(),
+        );
+        let manifest = ProjectManifest::parse(&manifest_path(), &text).unwrap();
+        let base = manifest_path().parent().unwrap().to_owned();
+
+        assert_eq!(manifest.base_directory(), base);
+        assert_eq!(manifest.game.assembly, Some(absolute));
+        assert_eq!(manifest.game.mods_directory, Some(base.join("build/mods")));
+        assert_eq!(manifest.engine.data_directories, vec![base.join("assets")]);
+    }
+
+    #[test]
+    fn runtime_section_is_accepted_with_or_without_dotnet() {
+        let text = "[runtime]\nassemblies = ['NT.dll']\n\n[runtime.properties]\n\"System.GC.Server\" = false\n";
+        assert!(ProjectManifest::parse(&manifest_path(), text).is_ok());
+    }
+
+    #[cfg(feature = "dotnet")]
+    #[test]
+    fn roll_forward_is_snake_case() {
+        let manifest = ProjectManifest::parse(&manifest_path(), "[runtime.framework]\nroll_forward = \"latest_minor\"\n").unwrap();
+        assert_eq!(manifest.runtime.framework.roll_forward, RollForward::LatestMinor);
+        assert_eq!(manifest.runtime_config().roll_forward, Some(RollForward::LatestMinor));
+
+        let result = ProjectManifest::parse(&manifest_path(), "[runtime.framework]\nroll_forward = \"LatestMinor\"\n");
+        assert!(matches!(result, Err(ManifestError::ParseFailed { .. })));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let result = ProjectManifest::parse(&manifest_path(), "[engine]\nfixed_time_step = 0.01\n");
+        assert!(matches!(result, Err(ManifestError::ParseFailed { .. })));
+
+        let result = ProjectManifest::parse(&manifest_path(), "[engnie]\n");
+        assert!(matches!(result, Err(ManifestError::ParseFailed { .. })));
+    }
+
+    #[test]
+    fn fixed_timestep_must_be_positive_and_finite() {
+        for value in ["-0.01", "0.0", "inf", "nan"] {
+            let result = ProjectManifest::parse(&manifest_path(), &format!("[engine]\nfixed_timestep = {}\n", value));
+            assert!(matches!(result, Err(ManifestError::Invalid { .. })), "{} was accepted", value);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::{collections::HashMap, fmt};
+
+use glow::HasContext;
+
+use crate::{
+    render::{math_types::{Color, Mat3, Mat4, Vec2, Vec3, Vec4}, shader_2d::Shader2D, Id, ShaderValueType},
+    util::string_name::StringName,
+};
+
+
+/// A value for one shader uniform
+///
+/// `Color` sets a `vec4`, `TextureSlot` sets a `sampler2D` to a texture unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum UniformBinding {
+    Float(f32),
+    Int(i32),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Vec4(Vec4),
+    Mat3(Mat3),
+    Mat4(Mat4),
+    Color(Color),
+    TextureSlot(i32),
+}
+
+#[derive(Clone, Debug)]
+pub enum Material2DError {
+    UnknownMaterial(Id),
+    UnknownShader(Id),
+    NoShader(Id),
+    UnknownParameter {
+        shader: Id,
+        name: StringName,
+    },
+    TypeMismatch {
+        name: StringName,
+        expected: ShaderValueType,
+        actual: ShaderValueType,
+    },
+    ArrayParameter {
+        name: StringName,
+        len: i32,
+    },
+}
+
+/// A shader and the uniform values to draw with it
+///
+/// Parameters are checked against the shader when they're set and uploaded when the material is
+/// bound. Array uniforms can't be set yet.
+pub(super) struct Material2D {
+    shader: Option<Id>,
+    uniform_bindings: HashMap<StringName, UniformBinding>,
+}
+
+impl UniformBinding {
+    pub fn value_type(&self) -> ShaderValueType {
+        match self {
+            Self::Float(_) => ShaderValueType::Float,
+            Self::Int(_) => ShaderValueType::Int,
+            Self::Vec2(_) => ShaderValueType::Vec2,
+            Self::Vec3(_) => ShaderValueType::Vec3,
+            Self::Vec4(_) | Self::Color(_) => ShaderValueType::Vec4,
+            Self::Mat3(_) => ShaderValueType::Mat3,
+            Self::Mat4(_) => ShaderValueType::Mat4,
+            Self::TextureSlot(_) => ShaderValueType::Sampler2D,
+        }
+    }
+
+    unsafe fn upload(&self, gl: &glow::Context, location: &glow::UniformLocation) {
+        let location = Some(location);
+        match *self {
+            Self::Float(value) => gl.uniform_1_f32(location, value),
+            Self::Int(value) | Self::TextureSlot(value) => gl.uniform_1_i32(location, value),
+            Self::Vec2(value) => gl.uniform_2_f32(location, value.x, value.y),
+            Self::Vec3(value) => gl.uniform_3_f32(location, value.x, value.y, value.z),
+            Self::Vec4(value) => gl.uniform_4_f32(location, value.x, value.y, value.z, value.w),
+            Self::Color(value) => gl.uniform_4_f32(location, value.r, value.g, value.b, value.a),
+            Self::Mat3(value) => gl.uniform_matrix_3_f32_slice(location, false, &value.to_cols_array()),
+            Self::Mat4(value) => gl.uniform_matrix_4_f32_slice(location, false, &value.to_cols_array()),
+        }
+    }
+}
+
+impl Material2D {
+    pub(super) fn new() -> Self {
+        Self {
+            shader: None,
+            uniform_bindings: HashMap::new(),
+        }
+    }
+
+    pub(super) fn shader(&self) -> Option<Id> {
+        self.shader
+    }
+
+    /// Parameters the new shader doesn't declare with the same type are dropped
+    pub(super) fn set_shader(&mut self, id: Id, shader: &Shader2D) {
+        self.shader = Some(id);
+        self.uniform_bindings.retain(|&name, binding| {
+            shader.uniform(name).is_some_and(|uniform| uniform.value_type == binding.value_type() && uniform.array_len == 1)
+        });
+    }
+
+    /// `shader` must be the material's shader
+    pub(super) fn set_param(&mut self, shader_id: Id, shader: &Shader2D, name: StringName, binding: UniformBinding) -> Result<(), Material2DError> {
+        let Some(uniform) = shader.uniform(name) else {
+            return Err(Material2DError::UnknownParameter { shader: shader_id, name });
+        };
+        if uniform.value_type != binding.value_type() {
+            return Err(Material2DError::TypeMismatch { name, expected: uniform.value_type, actual: binding.value_type() });
+        }
+        if uniform.array_len != 1 {
+            return Err(Material2DError::ArrayParameter { name, len: uniform.array_len });
+        }
+
+        self.uniform_bindings.insert(name, binding);
+        Ok(())
+    }
+
+    /// Makes `shader` current and uploads every parameter, `shader` must be the material's shader
+    pub(super) fn bind(&self, gl: &glow::Context, shader: &Shader2D) {
+        shader.use_program(gl);
+        for (&name, binding) in &self.uniform_bindings {
+            if let Some(location) = shader.uniform_location(name) {
+                unsafe { binding.upload(gl, location) };
+            }
+        }
+    }
+}
+
+impl fmt::Display for Material2DError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownMaterial(id) =>
+                write!(f, "no material with id {}", id),
+            Self::UnknownShader(id) =>
+                write!(f, "no shader with id {}", id),
+            Self::NoShader(id) =>
+                write!(f, "material {} has no shader, set one before its parameters", id),
+            Self::UnknownParameter { shader, name } =>
+                write!(f, "shader {} has no active uniform '{}', unused uniforms are optimized out", shader, name.as_str()),
+            Self::TypeMismatch { name, expected, actual } =>
+                write!(f, "uniform '{}' is a {:?}, can't set it to a {:?}", name.as_str(), expected, actual),
+            Self::ArrayParameter { name, len } =>
+                write!(f, "uniform '{}' is an array of {}, array uniforms can't be set yet", name.as_str(), len),
+        }
+    }
+}
+
+impl std::error::Error for Material2DError {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::ShaderParameter;
+
+    fn shader() -> Shader2D {
+        let uniform = |name, value_type, array_len| ShaderParameter { name: StringName::new(name), value_type, array_len };
+        Shader2D::reflected(vec![
+            uniform("u_tint", ShaderValueType::Vec4, 1),
+            uniform("u_texture", ShaderValueType::Sampler2D, 1),
+            uniform("u_lights", ShaderValueType::Vec3, 4),
+        ])
+    }
+
+    #[test]
+    fn set_param_checks_the_shader() {
+        let shader = shader();
+        let mut material = Material2D::new();
+        material.set_shader(1, &shader);
+
+        assert!(material.set_param(1, &shader, StringName::new("u_tint"), UniformBinding::Color(Color::WHITE)).is_ok());
+        assert!(material.set_param(1, &shader, StringName::new("u_texture"), UniformBinding::TextureSlot(0)).is_ok());
+        assert!(matches!(
+            material.set_param(1, &shader, StringName::new("u_missing"), UniformBinding::Float(1.0)),
+            Err(Material2DError::UnknownParameter { shader: 1, .. })
+        ));
+        assert!(matches!(
+            material.set_param(1, &shader, StringName::new("u_tint"), UniformBinding::Vec3(Vec3::ONE)),
+            Err(Material2DError::TypeMismatch { expected: ShaderValueType::Vec4, actual: ShaderValueType::Vec3, .. })
+        ));
+        assert!(matches!(
+            material.set_param(1, &shader, StringName::new("u_lights"), UniformBinding::Vec3(Vec3::ONE)),
+            Err(Material2DError::ArrayParameter { len: 4, .. })
+        ));
+        assert_eq!(material.uniform_bindings.len(), 2);
+    }
+
+    #[test]
+    fn set_shader_keeps_matching_params() {
+        let shader = shader();
+        let mut material = Material2D::new();
+        material.set_shader(1, &shader);
+        material.set_param(1, &shader, StringName::new("u_tint"), UniformBinding::Vec4(Vec4::ONE)).unwrap();
+        material.set_param(1, &shader, StringName::new("u_texture"), UniformBinding::TextureSlot(2)).unwrap();
+
+        let other = Shader2D::reflected(vec![
+            ShaderParameter { name: StringName::new("u_tint"), value_type: ShaderValueType::Vec4, array_len: 1 },
+            ShaderParameter { name: StringName::new("u_texture"), value_type: ShaderValueType::Int, array_len: 1 },
+        ]);
+        material.set_shader(2, &other);
+        assert_eq!(material.shader(), Some(2));
+        assert_eq!(material.uniform_bindings.len(), 1);
+        assert!(material.uniform_bindings.contains_key(&StringName::new("u_tint")));
+    }
+}
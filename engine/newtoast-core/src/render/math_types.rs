@@ -1,6 +1,153 @@
+use std::ops::Mul;
+
+use crate::runtime_interface::native_exports::native_struct;
 
 pub use glam::{
     Vec2, Vec3, Vec4,
     Mat2, Mat3, Mat4,
+    Affine2,
     Quat,
-};
\ No newline at end of file
+};
+
+
+native_struct! {
+    /// A 2D affine transform as a column-major 3x2 matrix
+    ///
+    /// `glam::Affine2` isn't usable across FFI, its `Mat2` is SIMD-aligned on some targets. This
+    /// is six plain floats, the same layout as GlmSharp's `mat3x2` on the managed side, and
+    /// converts to and from `Affine2` for the actual math.
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct Transform2D in "Transform2D" {
+        pub x_axis: Vec2,
+        pub y_axis: Vec2,
+        pub translation: Vec2,
+    }
+}
+
+native_struct! {
+    /// RGBA with every channel from 0 to 1
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct Color in "Color" {
+        pub r: f32,
+        pub g: f32,
+        pub b: f32,
+        pub a: f32,
+    }
+}
+
+// the managed side reads both as plain floats
+const _: () = assert!(std::mem::size_of::<Transform2D>() == 24 && std::mem::align_of::<Transform2D>() == 4);
+const _: () = assert!(std::mem::size_of::<Color>() == 16 && std::mem::align_of::<Color>() == 4);
+
+impl Color {
+    pub const WHITE: Self = Self::new(1.0, 1.0, 1.0, 1.0);
+    pub const BLACK: Self = Self::new(0.0, 0.0, 0.0, 1.0);
+    pub const TRANSPARENT: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+impl Transform2D {
+    pub const IDENTITY: Self = Self {
+        x_axis: Vec2::X,
+        y_axis: Vec2::Y,
+        translation: Vec2::ZERO,
+    };
+
+    pub fn from_translation(translation: Vec2) -> Self {
+        Self { translation, ..Self::IDENTITY }
+    }
+
+    /// Scales first, then rotates counter-clockwise by `angle` radians, then translates
+    pub fn from_scale_angle_translation(scale: Vec2, angle: f32, translation: Vec2) -> Self {
+        Affine2::from_scale_angle_translation(scale, angle, translation).into()
+    }
+
+    pub fn to_affine2(&self) -> Affine2 {
+        Affine2::from_cols(self.x_axis, self.y_axis, self.translation)
+    }
+
+    pub fn transform_point2(&self, point: Vec2) -> Vec2 {
+        self.x_axis * point.x + self.y_axis * point.y + self.translation
+    }
+
+    pub fn inverse(&self) -> Self {
+        self.to_affine2().inverse().into()
+    }
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Affine2> for Transform2D {
+    fn from(affine: Affine2) -> Self {
+        Self {
+            x_axis: affine.matrix2.x_axis,
+            y_axis: affine.matrix2.y_axis,
+            translation: affine.translation,
+        }
+    }
+}
+
+impl From<Transform2D> for Affine2 {
+    fn from(transform: Transform2D) -> Self {
+        transform.to_affine2()
+    }
+}
+
+/// `a * b` applies `b` first, like matrix multiplication
+impl Mul for Transform2D {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        (self.to_affine2() * rhs.to_affine2()).into()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> (Transform2D, Transform2D) {
+        (
+            Transform2D::from_scale_angle_translation(Vec2::new(2.0, 0.5), 0.7, Vec2::new(3.0, -1.0)),
+            Transform2D::from_scale_angle_translation(Vec2::new(1.5, 1.5), -1.2, Vec2::new(-4.0, 2.0)),
+        )
+    }
+
+    #[test]
+    fn mul_applies_the_right_hand_side_first() {
+        let (a, b) = sample();
+        let point = Vec2::new(0.25, -3.0);
+        assert_eq!((a * b).to_affine2(), a.to_affine2() * b.to_affine2());
+        assert!((a * b).transform_point2(point).abs_diff_eq(a.transform_point2(b.transform_point2(point)), 1e-5));
+    }
+
+    #[test]
+    fn transform_point2_matches_affine2() {
+        let (a, _) = sample();
+        for point in [Vec2::ZERO, Vec2::X, Vec2::new(-7.5, 2.25)] {
+            assert!(a.transform_point2(point).abs_diff_eq(a.to_affine2().transform_point2(point), 1e-5));
+        }
+    }
+
+    #[test]
+    fn inverse_matches_affine2() {
+        let (a, _) = sample();
+        let inverse = a.inverse();
+        assert_eq!(inverse.to_affine2(), a.to_affine2().inverse());
+        assert!((a * inverse).to_affine2().abs_diff_eq(Affine2::IDENTITY, 1e-5));
+    }
+
+    #[test]
+    fn affine2_round_trips() {
+        let (a, _) = sample();
+        assert_eq!(Transform2D::from(Affine2::from(a)), a);
+    }
+}
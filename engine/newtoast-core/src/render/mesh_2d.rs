@@ -0,0 +1,270 @@
+use std::fmt;
+
+use glow::HasContext;
+
+use crate::render::{math_types::{Color, Vec2}, Id};
+
+
+/// Attribute locations every 2D shader reads vertices from
+pub const ATTRIBUTE_POSITION: u32 = 0;
+pub const ATTRIBUTE_UV: u32 = 1;
+pub const ATTRIBUTE_COLOR: u32 = 2;
+
+/// Vertex and index arrays to upload, borrowed from wherever they live (managed memory included)
+///
+/// Missing UVs upload as zero and missing colors as white. Without indices the vertices are
+/// drawn as a plain triangle list.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Mesh2DData<'a> {
+    pub positions: &'a [Vec2],
+    pub uvs: Option<&'a [Vec2]>,
+    pub colors: Option<&'a [Color]>,
+    pub indices: Option<&'a [u32]>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Mesh2DError {
+    UnknownMesh(Id),
+    AttributeLengthMismatch {
+        attribute: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    IndexOutOfRange {
+        index: u32,
+        vertex_count: usize,
+    },
+    GpuAllocationFailed(String),
+}
+
+/// A mesh's GPU objects
+///
+/// Vertex attributes are stored back to back in one buffer, all positions, then all UVs, then
+/// all colors. Updates with the same vertex and index counts overwrite the buffers in place,
+/// anything else reallocates them.
+pub(super) struct Mesh2D {
+    vertex_array: glow::VertexArray,
+    vertex_buffer: glow::Buffer,
+    index_buffer: Option<glow::Buffer>,
+    vertex_count: usize,
+    index_count: usize,
+    usage: u32,
+}
+
+impl<'a> Mesh2DData<'a> {
+    fn validate(&self) -> Result<(), Mesh2DError> {
+        let vertex_count = self.positions.len();
+        let lengths = [
+            ("uvs", self.uvs.map(<[Vec2]>::len)),
+            ("colors", self.colors.map(<[Color]>::len)),
+        ];
+        for (attribute, length) in lengths {
+            if let Some(actual) = length.filter(|&actual| actual != vertex_count) {
+                return Err(Mesh2DError::AttributeLengthMismatch { attribute, expected: vertex_count, actual });
+            }
+        }
+
+        let out_of_range = self.indices.unwrap_or_default().iter().find(|&&index| index as usize >= vertex_count);
+        match out_of_range {
+            Some(&index) => Err(Mesh2DError::IndexOutOfRange { index, vertex_count }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Mesh2D {
+    /// `dynamic` hints that the mesh will be updated often
+    pub(super) fn new(gl: &glow::Context, data: &Mesh2DData, dynamic: bool) -> Result<Self, Mesh2DError> {
+        data.validate()?;
+
+        unsafe {
+            let vertex_array = gl.create_vertex_array().map_err(Mesh2DError::GpuAllocationFailed)?;
+            let vertex_buffer = match gl.create_buffer() {
+                Ok(buffer) => buffer,
+                Err(e) => {
+                    gl.delete_vertex_array(vertex_array);
+                    return Err(Mesh2DError::GpuAllocationFailed(e));
+                },
+            };
+
+            let mut mesh = Self {
+                vertex_array,
+                vertex_buffer,
+                index_buffer: None,
+                vertex_count: 0,
+                index_count: 0,
+                usage: if dynamic { glow::DYNAMIC_DRAW } else { glow::STATIC_DRAW },
+            };
+            // there's no Drop, GL objects need the context to be released
+            if let Err(e) = mesh.upload(gl, data, true) {
+                mesh.destroy(gl);
+                return Err(e);
+            }
+            Ok(mesh)
+        }
+    }
+
+    pub(super) fn update(&mut self, gl: &glow::Context, data: &Mesh2DData) -> Result<(), Mesh2DError> {
+        data.validate()?;
+        let reallocate = data.positions.len() != self.vertex_count
+            || data.indices.map_or(0, <[u32]>::len) != self.index_count
+            || data.indices.is_some() != self.index_buffer.is_some();
+        unsafe { self.upload(gl, data, reallocate) }
+    }
+
+    /// Draws a triangle list with whatever program is current
+    pub(super) fn draw(&self, gl: &glow::Context) {
+        unsafe {
+            gl.bind_vertex_array(Some(self.vertex_array));
+            match self.index_buffer {
+                Some(_) => gl.draw_elements(glow::TRIANGLES, self.index_count as i32, glow::UNSIGNED_INT, 0),
+                None => gl.draw_arrays(glow::TRIANGLES, 0, self.vertex_count as i32),
+            }
+            gl.bind_vertex_array(None);
+        }
+    }
+
+    pub(super) fn destroy(self, gl: &glow::Context) {
+        unsafe {
+            gl.delete_vertex_array(self.vertex_array);
+            gl.delete_buffer(self.vertex_buffer);
+            if let Some(index_buffer) = self.index_buffer {
+                gl.delete_buffer(index_buffer);
+            }
+        }
+    }
+
+    unsafe fn upload(&mut self, gl: &glow::Context, data: &Mesh2DData, reallocate: bool) -> Result<(), Mesh2DError> {
+        let vertex_count = data.positions.len();
+        let uvs_offset = std::mem::size_of_val(data.positions);
+        // sized by the vertex count, `data.uvs` may be missing
+        #[allow(clippy::manual_slice_size_calculation)]
+        let colors_offset = uvs_offset + vertex_count * std::mem::size_of::<Vec2>();
+        let size = colors_offset + vertex_count * std::mem::size_of::<Color>();
+
+        // the ARRAY_BUFFER binding isn't part of VAO state, the ELEMENT_ARRAY_BUFFER one is
+        gl.bind_vertex_array(Some(self.vertex_array));
+        gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vertex_buffer));
+
+        if reallocate {
+            gl.buffer_data_size(glow::ARRAY_BUFFER, size as i32, self.usage);
+
+            let attributes = [
+                (ATTRIBUTE_POSITION, 2, 0),
+                (ATTRIBUTE_UV, 2, uvs_offset),
+                (ATTRIBUTE_COLOR, 4, colors_offset),
+            ];
+            for (location, components, offset) in attributes {
+                gl.enable_vertex_attrib_array(location);
+                gl.vertex_attrib_pointer_f32(location, components, glow::FLOAT, false, 0, offset as i32);
+            }
+        }
+
+        gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER, 0, as_bytes(data.positions));
+        match data.uvs {
+            Some(uvs) => gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER, uvs_offset as i32, as_bytes(uvs)),
+            None => gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER, uvs_offset as i32, as_bytes(&vec![Vec2::ZERO; vertex_count])),
+        }
+        match data.colors {
+            Some(colors) => gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER, colors_offset as i32, as_bytes(colors)),
+            None => gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER, colors_offset as i32, as_bytes(&vec![Color::WHITE; vertex_count])),
+        }
+
+        let result = match (data.indices, self.index_buffer) {
+            (Some(indices), Some(index_buffer)) => {
+                gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(index_buffer));
+                if reallocate {
+                    gl.buffer_data_u8_slice(glow::ELEMENT_ARRAY_BUFFER, as_bytes(indices), self.usage);
+                } else {
+                    gl.buffer_sub_data_u8_slice(glow::ELEMENT_ARRAY_BUFFER, 0, as_bytes(indices));
+                }
+                Ok(())
+            },
+            (Some(indices), None) => gl.create_buffer().map(|index_buffer| {
+                gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(index_buffer));
+                gl.buffer_data_u8_slice(glow::ELEMENT_ARRAY_BUFFER, as_bytes(indices), self.usage);
+                self.index_buffer = Some(index_buffer);
+            }),
+            (None, Some(index_buffer)) => {
+                gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, None);
+                gl.delete_buffer(index_buffer);
+                self.index_buffer = None;
+                Ok(())
+            },
+            (None, None) => Ok(()),
+        };
+
+        gl.bind_vertex_array(None);
+        gl.bind_buffer(glow::ARRAY_BUFFER, None);
+
+        result.map_err(Mesh2DError::GpuAllocationFailed)?;
+        self.vertex_count = vertex_count;
+        self.index_count = data.indices.map_or(0, <[u32]>::len);
+        Ok(())
+    }
+}
+
+/// # Safety
+/// `T` must have no padding or other uninitialized bytes, like the float and integer types
+/// uploaded here
+unsafe fn as_bytes<T: Copy>(values: &[T]) -> &[u8] {
+    std::slice::from_raw_parts(values.as_ptr() as *const u8, std::mem::size_of_val(values))
+}
+
+impl fmt::Display for Mesh2DError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownMesh(id) =>
+                write!(f, "no mesh with id {}", id),
+            Self::AttributeLengthMismatch { attribute, expected, actual } =>
+                write!(f, "mesh has {} positions but {} {}", expected, actual, attribute),
+            Self::IndexOutOfRange { index, vertex_count } =>
+                write!(f, "mesh index {} is out of range for {} vertices", index, vertex_count),
+            Self::GpuAllocationFailed(message) =>
+                write!(f, "could not allocate mesh buffers: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Mesh2DError {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POSITIONS: [Vec2; 3] = [Vec2::ZERO, Vec2::X, Vec2::Y];
+
+    #[test]
+    fn optional_arrays_can_be_missing() {
+        let data = Mesh2DData { positions: &POSITIONS, ..Default::default() };
+        assert!(data.validate().is_ok());
+        assert!(Mesh2DData::default().validate().is_ok());
+    }
+
+    #[test]
+    fn attribute_lengths_must_match_the_positions() {
+        let uvs = [Vec2::ZERO; 2];
+        let data = Mesh2DData { positions: &POSITIONS, uvs: Some(&uvs), ..Default::default() };
+        assert!(matches!(
+            data.validate(),
+            Err(Mesh2DError::AttributeLengthMismatch { attribute: "uvs", expected: 3, actual: 2 })
+        ));
+
+        let colors = [Color::WHITE; 4];
+        let data = Mesh2DData { positions: &POSITIONS, colors: Some(&colors), ..Default::default() };
+        assert!(matches!(
+            data.validate(),
+            Err(Mesh2DError::AttributeLengthMismatch { attribute: "colors", expected: 3, actual: 4 })
+        ));
+    }
+
+    #[test]
+    fn indices_must_be_in_range() {
+        let data = Mesh2DData { positions: &POSITIONS, indices: Some(&[0, 1, 2]), ..Default::default() };
+        assert!(data.validate().is_ok());
+
+        let data = Mesh2DData { positions: &POSITIONS, indices: Some(&[0, 3, 1]), ..Default::default() };
+        assert!(matches!(data.validate(), Err(Mesh2DError::IndexOutOfRange { index: 3, vertex_count: 3 })));
+    }
+}
@@ -5,6 +5,15 @@ mod imgui_sdl;
 
 pub mod math_types;
 
+mod material_2d;
+pub use material_2d::{Material2DError, UniformBinding};
+
+mod mesh_2d;
+pub use mesh_2d::{Mesh2DData, Mesh2DError, ATTRIBUTE_COLOR, ATTRIBUTE_POSITION, ATTRIBUTE_UV};
+
+mod shader_2d;
+pub use shader_2d::{Shader2DError, ShaderParameter, ShaderValueType, UNIFORM_TRANSFORM, VERTEX_ATTRIBUTES};
+
 mod render_context;
 pub use render_context::*;
 
@@ -1,10 +1,10 @@
 
-use std::ffi::{CStr, CString};
+use std::{ffi::{CStr, CString}, rc::Rc};
 
 use glow::{HasContext};
 use sdl3_sys::everything::*;
 
-use crate::{render::{gl_get_proc_address, imgui_sdl::ImguiSdl}, runtime_interface::config::InitConfig};
+use crate::{render::{gl_get_proc_address, imgui_sdl::ImguiSdl, RenderServer}, runtime_interface::config::InitConfig};
 
 
 #[derive(Clone, Debug)]
@@ -18,7 +18,7 @@ pub enum RenderError {
 pub struct RenderContext {
     window: *mut SDL_Window,
     gl_context: *mut SDL_GLContextState,
-    gl: glow::Context,
+    gl: Rc<glow::Context>,
     imgui: imgui::Context,
     imgui_sdl: ImguiSdl,
 }
@@ -73,7 +73,7 @@ impl RenderContext {
                 sdl_err.insert_str(0, "SDL_GL_MakeCurrent failed: ");
                 return Err(RenderError::GLSetupFailed(sdl_err));
             }
-            let gl = glow::Context::from_loader_function(gl_get_proc_address);
+            let gl = Rc::new(glow::Context::from_loader_function(gl_get_proc_address));
 
 
             let mut imgui = imgui::Context::create();
@@ -99,6 +99,11 @@ impl RenderContext {
         }
     }
 
+    /// Shared with the `RenderServer`, which owns the GPU objects it creates through it
+    pub fn gl(&self) -> &Rc<glow::Context> {
+        &self.gl
+    }
+
     pub fn collect_events(&mut self) -> Vec<SDL_Event> {
         let mut events = Vec::new();
         unsafe {
@@ -128,10 +133,12 @@ impl RenderContext {
         f(ui)
     }
 
-    pub fn render_frame(&mut self) {
+    pub fn render_frame(&mut self, render_server: &RenderServer) {
         unsafe {
             self.gl.clear(glow::COLOR_BUFFER_BIT);
 
+            render_server.draw_2d();
+
             self.imgui_sdl.draw(&self.gl, &mut self.imgui);
 
             SDL_GL_SwapWindow(self.window);
@@ -1,24 +1,30 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, rc::Rc};
 
+use glow::HasContext;
 use smallvec::SmallVec;
 
+#[cfg(feature = "dotnet")]
+use crate::dotnet::ManagedHandle;
+use crate::{
+    render::{
+        material_2d::{Material2D, Material2DError, UniformBinding},
+        math_types::Transform2D,
+        mesh_2d::{Mesh2D, Mesh2DData, Mesh2DError},
+        shader_2d::{Shader2D, Shader2DError, ShaderParameter},
+    },
+    util::string_name::StringName,
+};
+
 
 pub type Id = u32;
-pub type Transform2D = u32; // FIXME placeholder
 
 struct Renderable2D {
     transform: Transform2D,
-    surfaces: SmallVec<[(Id, Id); 3]>
-}
-
-enum UniformBinding {
-    Float(f32),
-    Int(i32),
-}
-
-struct Material2D {
-    shader: Id,
-    uniform_bindings: HashMap<String, UniformBinding>,
+    // (material, mesh), drawn in order
+    surfaces: SmallVec<[(Id, Id); 3]>,
+    // the managed object this renderable belongs to, handed back in callbacks
+    #[cfg(feature = "dotnet")]
+    user_data: Option<ManagedHandle>,
 }
 
 pub struct RenderServer {
@@ -26,19 +32,21 @@ pub struct RenderServer {
     // - storage for GPU assets
     // - render command buffer
 
+    gl: Rc<glow::Context>,
     next_id: Id,
 
     renderables_2d: HashMap<Id, Renderable2D>,
-    materials_2d: HashMap<Id, ()>,
-    shaders_2d: HashMap<Id, ()>,
-    meshes_2d: HashMap<Id, ()>,
+    materials_2d: HashMap<Id, Material2D>,
+    shaders_2d: HashMap<Id, Shader2D>,
+    meshes_2d: HashMap<Id, Mesh2D>,
 
     render_targets: HashMap<Id, ()>,
 }
 
 impl RenderServer {
-    pub fn new() -> Self {
+    pub fn new(gl: Rc<glow::Context>) -> Self {
         Self {
+            gl,
             next_id: 1,
             renderables_2d: HashMap::new(),
             materials_2d: HashMap::new(),
@@ -52,8 +60,10 @@ impl RenderServer {
         let id = self.next_id;
         self.next_id += 1;
         self.renderables_2d.insert(id, Renderable2D {
-            transform: 0, // TODO identity
+            transform: Transform2D::IDENTITY,
             surfaces: SmallVec::new(),
+            #[cfg(feature = "dotnet")]
+            user_data: None,
         });
         id
     }
@@ -65,9 +75,37 @@ impl RenderServer {
     }
 
     pub fn renderable_2d_set_transform(&mut self, id: Id, transform: Transform2D) {
-        todo!()
+        if let Some(renderable) = self.renderables_2d.get_mut(&id) {
+            renderable.transform = transform;
+        }
+    }
+
+    /// Identity for a renderable that doesn't exist
+    pub fn renderable_2d_get_transform(&self, id: Id) -> Transform2D {
+        self.renderables_2d.get(&id).map_or(Transform2D::IDENTITY, |renderable| renderable.transform)
+    }
+
+    /// Replaces the renderable's user data, the previous handle is freed
+    ///
+    /// Hands `user_data` back if the renderable doesn't exist.
+    #[cfg(feature = "dotnet")]
+    pub fn renderable_2d_set_user_data(&mut self, id: Id, user_data: Option<ManagedHandle>) -> Result<(), Option<ManagedHandle>> {
+        match self.renderables_2d.get_mut(&id) {
+            Some(renderable) => {
+                renderable.user_data = user_data;
+                Ok(())
+            },
+            None => Err(user_data),
+        }
     }
 
+    #[cfg(feature = "dotnet")]
+    pub fn renderable_2d_get_user_data(&self, id: Id) -> Option<&ManagedHandle> {
+        self.renderables_2d.get(&id).and_then(|renderable| renderable.user_data.as_ref())
+    }
+
+    // render targets stay out of the native API until they're implemented, a panic can't unwind
+    // through an extern "C" export
     pub fn renderable_2d_set_target(&self, id: Id) -> Id {
         todo!()
     }
@@ -77,26 +115,147 @@ impl RenderServer {
     }
 
     pub fn material_2d_create(&mut self) -> Id {
-        todo!()
+        let id = self.next_id;
+        self.next_id += 1;
+        self.materials_2d.insert(id, Material2D::new());
+        id
     }
 
     pub fn material_2d_destroy(&mut self, id: Id) {
-        todo!()
+        self.materials_2d.remove(&id);
     }
 
-    pub fn material_2d_set_shader(&mut self, id: Id, shader: Id) {
-        todo!()
+    /// Keeps the parameters the new shader declares with the same type, drops the rest
+    pub fn material_2d_set_shader(&mut self, id: Id, shader: Id) -> Result<(), Material2DError> {
+        let material = self.materials_2d.get_mut(&id).ok_or(Material2DError::UnknownMaterial(id))?;
+        let shader_2d = self.shaders_2d.get(&shader).ok_or(Material2DError::UnknownShader(shader))?;
+        material.set_shader(shader, shader_2d);
+        Ok(())
     }
 
-    pub fn renderable_2d_add_surface(&mut self, renderable: Id, material: Id, mesh: Id) -> Id {
-        todo!()
+    /// Fails if the material's shader has no active uniform `name` of the binding's type
+    pub fn material_2d_set_shader_param(&mut self, id: Id, name: StringName, binding: UniformBinding) -> Result<(), Material2DError> {
+        let material = self.materials_2d.get_mut(&id).ok_or(Material2DError::UnknownMaterial(id))?;
+        let shader = material.shader().ok_or(Material2DError::NoShader(id))?;
+        let shader_2d = self.shaders_2d.get(&shader).ok_or(Material2DError::UnknownShader(shader))?;
+        material.set_param(shader, shader_2d, name, binding)
+    }
+
+    /// Makes the material's shader current and uploads its parameters, `draw_2d` does this for
+    /// every surface
+    pub fn material_2d_bind(&self, id: Id) -> Result<(), Material2DError> {
+        self.bind_material(id).map(|_| ())
+    }
+
+    fn bind_material(&self, id: Id) -> Result<&Shader2D, Material2DError> {
+        let material = self.materials_2d.get(&id).ok_or(Material2DError::UnknownMaterial(id))?;
+        let shader = material.shader().ok_or(Material2DError::NoShader(id))?;
+        let shader_2d = self.shaders_2d.get(&shader).ok_or(Material2DError::UnknownShader(shader))?;
+        material.bind(&self.gl, shader_2d);
+        Ok(shader_2d)
+    }
+
+    /// False if the renderable doesn't exist, the material and mesh are only looked up when drawing
+    pub fn renderable_2d_add_surface(&mut self, renderable: Id, material: Id, mesh: Id) -> bool {
+        match self.renderables_2d.get_mut(&renderable) {
+            Some(renderable) => {
+                renderable.surfaces.push((material, mesh));
+                true
+            },
+            None => false,
+        }
     }
 
     pub fn renderable_2d_clear_surfaces(&mut self, renderable: Id) {
-        todo!()
+        if let Some(renderable) = self.renderables_2d.get_mut(&renderable) {
+            renderable.surfaces.clear();
+        }
+    }
+
+    /// Draws every renderable's surfaces, renderables in creation order
+    ///
+    /// Each surface binds its material and sets `UNIFORM_TRANSFORM` to the renderable's transform.
+    /// Surfaces whose mesh or material is gone, or whose material has no shader, are skipped.
+    pub(super) fn draw_2d(&self) {
+        let mut ids: Vec<Id> = self.renderables_2d.keys().copied().collect();
+        ids.sort_unstable();
+
+        unsafe {
+            self.gl.enable(glow::BLEND);
+            self.gl.blend_func(glow::SRC_ALPHA, glow::ONE_MINUS_SRC_ALPHA);
+        }
+        for id in ids {
+            let renderable = &self.renderables_2d[&id];
+            for &(material, mesh) in &renderable.surfaces {
+                let Some(mesh) = self.meshes_2d.get(&mesh) else {
+                    continue;
+                };
+                let Ok(shader) = self.bind_material(material) else {
+                    continue;
+                };
+                shader.set_transform(&self.gl, renderable.transform);
+                mesh.draw(&self.gl);
+            }
+        }
+        unsafe {
+            self.gl.use_program(None);
+        }
+    }
+
+    /// Compiles and links a shader, binding `VERTEX_ATTRIBUTES` to their mesh locations
+    pub fn shader_2d_create(&mut self, vertex_src: &str, fragment_src: &str) -> Result<Id, Shader2DError> {
+        let shader = Shader2D::new(&self.gl, vertex_src, fragment_src)?;
+        let id = self.next_id;
+        self.next_id += 1;
+        self.shaders_2d.insert(id, shader);
+        Ok(id)
+    }
+
+    pub fn shader_2d_destroy(&mut self, id: Id) {
+        if let Some(shader) = self.shaders_2d.remove(&id) {
+            shader.destroy(&self.gl);
+        }
+    }
+
+    pub fn shader_2d_get_uniforms(&self, id: Id) -> Result<&[ShaderParameter], Shader2DError> {
+        self.shaders_2d.get(&id).map(Shader2D::uniforms).ok_or(Shader2DError::UnknownShader(id))
+    }
+
+    pub fn shader_2d_get_attributes(&self, id: Id) -> Result<&[ShaderParameter], Shader2DError> {
+        self.shaders_2d.get(&id).map(Shader2D::attributes).ok_or(Shader2DError::UnknownShader(id))
+    }
+
+    /// `dynamic` hints that the mesh will be updated often, see `mesh_2d_update`
+    pub fn mesh_2d_create(&mut self, data: &Mesh2DData, dynamic: bool) -> Result<Id, Mesh2DError> {
+        let mesh = Mesh2D::new(&self.gl, data, dynamic)?;
+        let id = self.next_id;
+        self.next_id += 1;
+        self.meshes_2d.insert(id, mesh);
+        Ok(id)
+    }
+
+    /// Overwrites the mesh's buffers in place when the vertex and index counts are unchanged
+    pub fn mesh_2d_update(&mut self, id: Id, data: &Mesh2DData) -> Result<(), Mesh2DError> {
+        match self.meshes_2d.get_mut(&id) {
+            Some(mesh) => mesh.update(&self.gl, data),
+            None => Err(Mesh2DError::UnknownMesh(id)),
+        }
     }
 
     pub fn mesh_2d_destroy(&mut self, id: Id) {
-        todo!()
+        if let Some(mesh) = self.meshes_2d.remove(&id) {
+            mesh.destroy(&self.gl);
+        }
+    }
+}
+
+impl Drop for RenderServer {
+    fn drop(&mut self) {
+        for (_, mesh) in self.meshes_2d.drain() {
+            mesh.destroy(&self.gl);
+        }
+        for (_, shader) in self.shaders_2d.drain() {
+            shader.destroy(&self.gl);
+        }
     }
 }
\ No newline at end of file
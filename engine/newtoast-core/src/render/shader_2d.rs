@@ -0,0 +1,226 @@
+use std::{collections::HashMap, fmt};
+
+use glow::HasContext;
+
+use crate::{
+    render::{math_types::{Mat3, Transform2D}, Id, ATTRIBUTE_COLOR, ATTRIBUTE_POSITION, ATTRIBUTE_UV},
+    util::string_name::StringName,
+};
+
+
+/// Vertex inputs a 2D shader can declare, bound to the locations `Mesh2D` uploads to
+pub const VERTEX_ATTRIBUTES: [(u32, &str); 3] = [
+    (ATTRIBUTE_POSITION, "a_position"),
+    (ATTRIBUTE_UV, "a_uv"),
+    (ATTRIBUTE_COLOR, "a_color"),
+];
+
+/// A `mat3` uniform the 2D pass sets to each renderable's transform, if the shader declares it
+pub const UNIFORM_TRANSFORM: &str = "u_transform";
+
+/// The GLSL type of a uniform or attribute, mirrored by `ShaderValueType` in C#
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ShaderValueType {
+    /// Anything the material system can't set, e.g. `bvec2` or `sampler3D`
+    Unsupported = 0,
+    Float,
+    Int,
+    Vec2,
+    Vec3,
+    Vec4,
+    Mat3,
+    Mat4,
+    Sampler2D,
+}
+
+/// An active uniform or attribute, as reflected after linking
+///
+/// Arrays are reported once under their base name, `u_lights` rather than `u_lights[0]`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ShaderParameter {
+    pub name: StringName,
+    pub value_type: ShaderValueType,
+    pub array_len: i32,
+}
+
+#[derive(Clone, Debug)]
+pub enum Shader2DError {
+    UnknownShader(Id),
+    CompileFailed {
+        stage: &'static str,
+        log: String,
+    },
+    LinkFailed(String),
+    GpuAllocationFailed(String),
+}
+
+/// A linked program and what it declares
+pub(super) struct Shader2D {
+    program: glow::Program,
+    uniforms: Vec<ShaderParameter>,
+    attributes: Vec<ShaderParameter>,
+    uniform_locations: HashMap<StringName, glow::UniformLocation>,
+    transform_location: Option<glow::UniformLocation>,
+}
+
+impl ShaderValueType {
+    fn from_gl(gl_type: u32) -> Self {
+        match gl_type {
+            glow::FLOAT => Self::Float,
+            glow::INT => Self::Int,
+            glow::FLOAT_VEC2 => Self::Vec2,
+            glow::FLOAT_VEC3 => Self::Vec3,
+            glow::FLOAT_VEC4 => Self::Vec4,
+            glow::FLOAT_MAT3 => Self::Mat3,
+            glow::FLOAT_MAT4 => Self::Mat4,
+            glow::SAMPLER_2D => Self::Sampler2D,
+            _ => Self::Unsupported,
+        }
+    }
+}
+
+impl Shader2D {
+    pub(super) fn new(gl: &glow::Context, vertex_src: &str, fragment_src: &str) -> Result<Self, Shader2DError> {
+        unsafe {
+            let vertex = compile(gl, glow::VERTEX_SHADER, "vertex", vertex_src)?;
+            let fragment = match compile(gl, glow::FRAGMENT_SHADER, "fragment", fragment_src) {
+                Ok(fragment) => fragment,
+                Err(e) => {
+                    gl.delete_shader(vertex);
+                    return Err(e);
+                },
+            };
+
+            let program = link(gl, vertex, fragment);
+            // the program keeps what it needs, the stages can go either way
+            gl.delete_shader(vertex);
+            gl.delete_shader(fragment);
+            let program = program?;
+
+            let mut uniforms = Vec::new();
+            let mut uniform_locations = HashMap::new();
+            for uniform in (0..gl.get_active_uniforms(program)).filter_map(|index| gl.get_active_uniform(program, index)) {
+                let parameter = parameter(&uniform.name, uniform.utype, uniform.size);
+                if let Some(location) = gl.get_uniform_location(program, &uniform.name) {
+                    uniform_locations.insert(parameter.name, location);
+                }
+                uniforms.push(parameter);
+            }
+            let attributes = (0..gl.get_active_attributes(program))
+                .filter_map(|index| gl.get_active_attribute(program, index))
+                .map(|attribute| parameter(&attribute.name, attribute.atype, attribute.size))
+                .collect();
+            let transform_location = gl.get_uniform_location(program, UNIFORM_TRANSFORM);
+
+            Ok(Self { program, uniforms, attributes, uniform_locations, transform_location })
+        }
+    }
+
+    pub(super) fn destroy(self, gl: &glow::Context) {
+        unsafe {
+            gl.delete_program(self.program);
+        }
+    }
+
+    pub(super) fn use_program(&self, gl: &glow::Context) {
+        unsafe {
+            gl.use_program(Some(self.program));
+        }
+    }
+
+    /// Sets `UNIFORM_TRANSFORM`, the program must be current
+    pub(super) fn set_transform(&self, gl: &glow::Context, transform: Transform2D) {
+        if let Some(location) = &self.transform_location {
+            let matrix = Mat3::from(transform.to_affine2());
+            unsafe { gl.uniform_matrix_3_f32_slice(Some(location), false, &matrix.to_cols_array()) };
+        }
+    }
+
+    pub(super) fn uniform(&self, name: StringName) -> Option<&ShaderParameter> {
+        self.uniforms.iter().find(|uniform| uniform.name == name)
+    }
+
+    /// `None` for uniforms in a uniform block, which have no location
+    pub(super) fn uniform_location(&self, name: StringName) -> Option<&glow::UniformLocation> {
+        self.uniform_locations.get(&name)
+    }
+
+    pub(super) fn uniforms(&self) -> &[ShaderParameter] {
+        &self.uniforms
+    }
+
+    pub(super) fn attributes(&self) -> &[ShaderParameter] {
+        &self.attributes
+    }
+
+    /// A shader that only exists as its reflected uniforms, for testing without a GL context
+    #[cfg(test)]
+    pub(super) fn reflected(uniforms: Vec<ShaderParameter>) -> Self {
+        Self {
+            program: glow::NativeProgram(std::num::NonZeroU32::MIN),
+            uniforms,
+            attributes: Vec::new(),
+            uniform_locations: HashMap::new(),
+            transform_location: None,
+        }
+    }
+}
+
+unsafe fn compile(gl: &glow::Context, shader_type: u32, stage: &'static str, src: &str) -> Result<glow::Shader, Shader2DError> {
+    let shader = gl.create_shader(shader_type).map_err(Shader2DError::GpuAllocationFailed)?;
+    gl.shader_source(shader, src);
+    gl.compile_shader(shader);
+    if gl.get_shader_compile_status(shader) {
+        return Ok(shader);
+    }
+
+    let log = gl.get_shader_info_log(shader);
+    gl.delete_shader(shader);
+    Err(Shader2DError::CompileFailed { stage, log })
+}
+
+unsafe fn link(gl: &glow::Context, vertex: glow::Shader, fragment: glow::Shader) -> Result<glow::Program, Shader2DError> {
+    let program = gl.create_program().map_err(Shader2DError::GpuAllocationFailed)?;
+    gl.attach_shader(program, vertex);
+    gl.attach_shader(program, fragment);
+    // binding names the shader doesn't declare is fine, they're ignored
+    for (location, name) in VERTEX_ATTRIBUTES {
+        gl.bind_attrib_location(program, location, name);
+    }
+    gl.link_program(program);
+    gl.detach_shader(program, vertex);
+    gl.detach_shader(program, fragment);
+    if gl.get_program_link_status(program) {
+        return Ok(program);
+    }
+
+    let log = gl.get_program_info_log(program);
+    gl.delete_program(program);
+    Err(Shader2DError::LinkFailed(log))
+}
+
+fn parameter(name: &str, gl_type: u32, array_len: i32) -> ShaderParameter {
+    ShaderParameter {
+        name: StringName::new(name.strip_suffix("[0]").unwrap_or(name)),
+        value_type: ShaderValueType::from_gl(gl_type),
+        array_len,
+    }
+}
+
+impl fmt::Display for Shader2DError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownShader(id) =>
+                write!(f, "no shader with id {}", id),
+            Self::CompileFailed { stage, log } =>
+                write!(f, "{} shader failed to compile:\n{}", stage, log),
+            Self::LinkFailed(log) =>
+                write!(f, "shader failed to link:\n{}", log),
+            Self::GpuAllocationFailed(message) =>
+                write!(f, "could not allocate shader objects: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Shader2DError {}
@@ -1,6 +1,11 @@
+use std::path::PathBuf;
+
 use lazy_static::lazy_static;
+use serde::Deserialize;
+#[cfg(feature = "dotnet")]
 use widestring::{U16CStr, U16CString};
 
+#[cfg(feature = "dotnet")]
 #[repr(C)]
 pub struct RawInitConfig {
     pub window_title: *const i16,
@@ -10,6 +15,20 @@ pub struct RawInitConfig {
     pub windowed: bool,
 }
 
+#[cfg(feature = "dotnet")]
+impl Default for RawInitConfig {
+    fn default() -> Self {
+        Self {
+            window_title: std::ptr::null(),
+            window_width: 0,
+            window_height: 0,
+            vsync: false,
+            windowed: false,
+        }
+    }
+}
+
+#[cfg(feature = "dotnet")]
 impl RawInitConfig {
     pub fn cook<F>(self, free_hstr: F) -> InitConfig
         where F: Fn(*const i16) -> ()
@@ -43,9 +62,49 @@ pub struct InitConfig {
     pub windowed: bool,
 }
 
-#[derive(Debug, Default)]
+/// What the engine does when a managed lifecycle call throws
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManagedErrorPolicy {
+    /// Stop calling Update/Draw until the game assembly is reloaded
+    Pause,
+    /// Keep running and show the latest exception in an overlay
+    #[default]
+    Overlay,
+}
+
+// matches InitConfigBuilder's defaults on the managed side
+impl Default for InitConfig {
+    fn default() -> Self {
+        Self {
+            window_title: "NewToast Application".into(),
+            window_width: 320,
+            window_height: 240,
+            vsync: true,
+            windowed: true,
+        }
+    }
+}
+
+#[derive(Debug)]
 pub struct Config {
+    pub managed_error_policy: ManagedErrorPolicy,
+    /// Mods are loaded from here after the game, see `plugin::discover_mods`
+    pub mods_directory: Option<PathBuf>,
+    /// Seconds per Update call
+    pub fixed_timestep: f64,
+    pub data_directories: Vec<PathBuf>,
+}
 
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            managed_error_policy: ManagedErrorPolicy::default(),
+            mods_directory: None,
+            fixed_timestep: 1.0 / 60.0,
+            data_directories: Vec::new(),
+        }
+    }
 }
 
 
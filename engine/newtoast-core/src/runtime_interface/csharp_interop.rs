@@ -0,0 +1,203 @@
+use std::{fmt::Write, io, path::Path};
+
+use crate::runtime_interface::{native_api::{NATIVE_API_STRUCTS, NATIVE_API_TABLES, NATIVE_API_VERSION}, native_exports::{NativeExport, NativeStruct, NativeTable}};
+
+
+const HEADER: &str = "\
+// <auto-generated>
+// Generated from the engine's native_exports! tables, regenerate with `just generate-interop`.
+// </auto-generated>
+";
+
+/// The managed half of `NativeApi`
+///
+/// The `native_struct!` layouts, the table structs, a forwarding `{Table}Internal` class per table and the public wrappers of
+/// `@bound` entries. Everything is `partial` where hand-written code has to add to it.
+pub fn generate_csharp_interop() -> String {
+    let mut out = String::new();
+    out.push_str(HEADER);
+    out.push_str("\nusing System;\nusing System.Runtime.InteropServices;\nusing GlmSharp;\n\nnamespace NTF;\n");
+
+    for native_struct in NATIVE_API_STRUCTS {
+        write_struct(&mut out, native_struct);
+    }
+    write_native_api(&mut out);
+    for table in NATIVE_API_TABLES {
+        write_table(&mut out, table);
+        write_internal_class(&mut out, table);
+        if table.functions.iter().any(|function| function.bound) {
+            write_wrapper_class(&mut out, table);
+        }
+    }
+    out
+}
+
+/// Regenerates the file at `path`, returns whether its contents changed
+pub fn write_csharp_interop(path: &Path) -> io::Result<bool> {
+    let generated = generate_csharp_interop();
+    if is_current(path, &generated)? {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, generated)?;
+    Ok(true)
+}
+
+/// Whether the file at `path` is what `generate_csharp_interop` produces, a missing file isn't
+pub fn is_csharp_interop_current(path: &Path) -> io::Result<bool> {
+    is_current(path, &generate_csharp_interop())
+}
+
+fn is_current(path: &Path, generated: &str) -> io::Result<bool> {
+    match std::fs::read_to_string(path) {
+        // git may have checked the file out with CRLF endings
+        Ok(existing) => Ok(existing.replace("\r\n", "\n") == generated),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn write_struct(out: &mut String, native_struct: &NativeStruct) {
+    out.push('\n');
+    out.push_str("[StructLayout(LayoutKind.Sequential)]\n");
+    writeln!(out, "public partial struct {}\n{{", native_struct.name).unwrap();
+    for field in native_struct.fields {
+        writeln!(out, "    public {} {};", (field.cs_type)(), pascal_case(field.name)).unwrap();
+    }
+    out.push_str("}\n");
+}
+
+fn write_native_api(out: &mut String) {
+    out.push('\n');
+    out.push_str("[StructLayout(LayoutKind.Sequential)]\n");
+    out.push_str("internal unsafe partial struct NativeApi\n{\n");
+    writeln!(out, "    public const uint Version = {};", NATIVE_API_VERSION).unwrap();
+    out.push('\n');
+    out.push_str("    public uint ApiVersion;\n");
+    out.push_str("    public uint Size;\n");
+    for table in NATIVE_API_TABLES {
+        writeln!(out, "    public {}Api {};", table.name, table.name).unwrap();
+    }
+    out.push_str("}\n");
+}
+
+fn write_table(out: &mut String, table: &NativeTable) {
+    out.push('\n');
+    out.push_str("[StructLayout(LayoutKind.Sequential)]\n");
+    writeln!(out, "internal unsafe struct {}Api\n{{", table.name).unwrap();
+    for function in table.functions {
+        let mut types: Vec<String> = function.params.iter().map(|param| (param.cs_type)()).collect();
+        types.push(return_type(function));
+        writeln!(out, "    public delegate* unmanaged<{}> {};", types.join(", "), pascal_case(function.name)).unwrap();
+    }
+    out.push_str("}\n");
+}
+
+fn write_internal_class(out: &mut String, table: &NativeTable) {
+    out.push('\n');
+    writeln!(out, "internal static unsafe class {}Internal\n{{", table.name).unwrap();
+    writeln!(out, "    static ref readonly {}Api Api => ref NativeApi.Current.{};", table.name, table.name).unwrap();
+
+    for function in table.functions {
+        let params: Vec<String> = function.params.iter()
+            .map(|param| format!("{} {}", (param.cs_type)(), camel_case(param.name)))
+            .collect();
+        let args: Vec<String> = function.params.iter().map(|param| camel_case(param.name)).collect();
+
+        out.push('\n');
+        write_doc(out, function);
+        writeln!(out, "    internal static {} {}({})", return_type(function), pascal_case(function.name), params.join(", ")).unwrap();
+        writeln!(out, "        => Api.{}({});", pascal_case(function.name), args.join(", ")).unwrap();
+    }
+    out.push_str("}\n");
+}
+
+/// `@bound` entries without their receiver, which comes from `Bound{Table}` instead
+fn write_wrapper_class(out: &mut String, table: &NativeTable) {
+    out.push('\n');
+    writeln!(out, "public static partial class {}\n{{", table.name).unwrap();
+
+    let mut first = true;
+    for function in table.functions.iter().filter(|function| function.bound) {
+        let Some((_receiver, params)) = function.params.split_first() else {
+            panic!("@bound {} needs a receiver as its first parameter", function.name);
+        };
+
+        let param_list: Vec<String> = params.iter()
+            .map(|param| format!("{} {}", (param.cs_type)(), camel_case(param.name)))
+            .collect();
+        let mut args = vec![format!("Bound{}", table.name)];
+        args.extend(params.iter().map(|param| camel_case(param.name)));
+
+        let ret = return_type(function);
+        let is_unsafe = ret.contains('*') || param_list.iter().any(|param| param.contains('*'));
+        let call = format!("{}Internal.{}({})", table.name, pascal_case(function.name), args.join(", "));
+
+        if !first {
+            out.push('\n');
+        }
+        first = false;
+        write_doc(out, function);
+        writeln!(out, "    public static {}{} {}({})", if is_unsafe { "unsafe " } else { "" }, ret, pascal_case(function.name), param_list.join(", ")).unwrap();
+        out.push_str("    {\n");
+        writeln!(out, "        if (!Is{}Bound)", table.name).unwrap();
+        writeln!(out, "            throw new InvalidOperationException(\"{} is not bound.\");", table.name).unwrap();
+        out.push('\n');
+        match function.ret {
+            Some(_) => writeln!(out, "        return {};", call).unwrap(),
+            None => writeln!(out, "        {};", call).unwrap(),
+        }
+        out.push_str("    }\n");
+    }
+    out.push_str("}\n");
+}
+
+fn write_doc(out: &mut String, function: &NativeExport) {
+    for line in function.doc {
+        writeln!(out, "    //{}", line.trim_end()).unwrap();
+    }
+}
+
+fn return_type(function: &NativeExport) -> String {
+    function.ret.map_or_else(|| "void".into(), |cs_type| cs_type())
+}
+
+/// `renderable_2d_create` to `Renderable2DCreate`, segments starting with a digit are uppercased
+fn pascal_case(snake: &str) -> String {
+    snake.split('_')
+        .map(|segment| {
+            let mut chars = segment.chars();
+            match chars.next() {
+                Some(c) if c.is_ascii_digit() => segment.to_ascii_uppercase(),
+                Some(c) => c.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn camel_case(snake: &str) -> String {
+    let pascal = pascal_case(snake);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(c) => c.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => pascal,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKED_IN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../framework/src/Generated/NativeApi.g.cs");
+
+    #[test]
+    fn generated_interop_is_current() {
+        let current = is_csharp_interop_current(Path::new(CHECKED_IN))
+            .unwrap_or_else(|e| panic!("could not read {}: {}", CHECKED_IN, e));
+        assert!(current, "{} is stale, regenerate it with `just generate-interop`", CHECKED_IN);
+    }
+}
@@ -2,7 +2,7 @@ use std::{ffi::{CStr, CString, OsString}, mem::transmute, os::raw::{c_char, c_vo
 
 use widestring::U16CString;
 
-use crate::{dotnet::{DotnetContext, DotnetFunction, DotnetFunctionPtr}, render::RenderServer, runtime_interface::config::{InitConfig, RawInitConfig}, util::parse_hstr_wide};
+use crate::{dotnet::{managed_bindings, DotnetContext, DotnetError, DotnetFunction, DotnetFunctionPtr, MainThread}, render::RenderServer, runtime_interface::{config::{InitConfig, RawInitConfig}, managed_error::{catch_managed, RawManagedError}, native_api::NativeApi}, util::parse_hstr_wide};
 
 
 #[repr(C)]
@@ -22,127 +22,100 @@ impl EntrypointSpecifier {
     }
 }
 
-type GetConfigFn = fn() -> RawInitConfig;
-type InitializeFn = fn();
-type ShutdownFn = fn();
-type UpdateFn = fn(f64);
-type DrawFn = fn();
-type FreeHstrFn = fn(*const i16);
+/// Index of a plugin entrypoint on the managed side, never reused after the plugin is unloaded
+pub type PluginId = i32;
 
-type BindSingletonFn = fn(*const c_void) -> ();
-type UnbindSingletonFn = fn() -> ();
+managed_bindings! {
+    /// Lifecycle calls into `NTEntrypointInternal`, every method there is [UnmanagedCallersOnly]
+    ///
+    /// Plugin entrypoints and their load contexts are static on the managed side and not
+    /// synchronized, so everything but `free_hstr` is main-thread-only. Lifecycle methods catch
+    /// their own exceptions and return a status, see `catch_managed`.
+    pub struct RuntimeEntrypoints in "NTF.NTEntrypointInternal, NT" {
+        @main_thread fn load_plugin_raw = "LoadPlugin"(path: *const u16, entry_class: *const u16, out_plugin_id: *mut PluginId, error: *mut RawManagedError) -> i32;
+        @main_thread fn unload_plugin_raw = "UnloadPlugin"(plugin_id: PluginId, error: *mut RawManagedError) -> i32;
 
-pub struct RuntimeEntrypoints {
-    get_config_fptr: DotnetFunctionPtr,
-    initialize_fptr: DotnetFunctionPtr,
-    shutdown_fptr: DotnetFunctionPtr,
-    update_fptr: DotnetFunctionPtr,
-    draw_fptr: DotnetFunctionPtr,
+        @main_thread fn plugin_config_raw = "GetPluginConfig"(plugin_id: PluginId, out_config: *mut RawInitConfig, error: *mut RawManagedError) -> i32;
+        @main_thread fn initialize_raw = "Initialize"(plugin_id: PluginId, error: *mut RawManagedError) -> i32;
+        @main_thread fn shutdown_raw = "Shutdown"(plugin_id: PluginId, error: *mut RawManagedError) -> i32;
+        @main_thread fn update_raw = "Update"(plugin_id: PluginId, delta_time: f64, error: *mut RawManagedError) -> i32;
+        @main_thread fn draw_raw = "Draw"(plugin_id: PluginId, error: *mut RawManagedError) -> i32;
 
-    free_hstr_fptr: DotnetFunctionPtr,
+        fn free_hstr = "FreeHstr"(hstr: *const i16);
 
-    bind_render_server: DotnetFunctionPtr,
-    unbind_render_server: DotnetFunctionPtr,
+        @main_thread fn bind_native_api_raw = "BindNativeApi"(api: *const NativeApi, error: *mut RawManagedError) -> i32;
+
+        @main_thread fn bind_render_server_raw = "BindRenderServer"(render_server: *const c_void, error: *mut RawManagedError) -> i32;
+        @main_thread fn unbind_render_server_raw = "UnbindRenderServer"(error: *mut RawManagedError) -> i32;
+    }
 }
 
 impl RuntimeEntrypoints {
-    pub fn find_entrypoint_and_build(
-        dotnet: &mut DotnetContext
-    ) -> Result<Self, &'static str> {
-        let internal_entrypoint_class = "NTF.NTEntrypointInternal, NT";
-
-        let get_config_fptr: DotnetFunctionPtr = dotnet.get_fn_pointer(
-            internal_entrypoint_class,
-            "SetupAndGetConfig",
-            "NTF.GetConfigFn, NT");
-        let initialize_fptr: DotnetFunctionPtr = dotnet.get_fn_pointer(
-            internal_entrypoint_class,
-            "Initialize",
-            "NTF.InitializeFn, NT");
-        let shutdown_fptr = dotnet.get_fn_pointer(
-            internal_entrypoint_class,
-            "Shutdown",
-            "NTF.ShutdownFn, NT");
-        let update_fptr = dotnet.get_fn_pointer(
-            internal_entrypoint_class,
-            "Update",
-            "NTF.UpdateFn, NT");
-        let draw_fptr = dotnet.get_fn_pointer(
-            internal_entrypoint_class,
-            "Draw",
-            "NTF.DrawFn, NT");
-
-        let free_hstr_fptr = dotnet.get_fn_pointer(
-            internal_entrypoint_class,
-            "FreeHstr",
-            "NTF.FreeHstrFn, NT");
-
-        let bind_render_server = dotnet.get_fn_pointer(
-            internal_entrypoint_class,
-            "BindRenderServer",
-            "NTF.BindRenderServerFn, NT");
-        let unbind_render_server = dotnet.get_fn_pointer(
-            internal_entrypoint_class,
-            "UnbindRenderServer",
-            "NTF.UnbindRenderServerFn, NT");
-
-        Ok(Self {
-            get_config_fptr,
-            initialize_fptr,
-            shutdown_fptr,
-            update_fptr,
-            draw_fptr,
-            free_hstr_fptr,
-            bind_render_server,
-            unbind_render_server,
-        })
+    /// Loads an assembly into its own collectible load context and constructs its entrypoint
+    ///
+    /// Without a path the entrypoint is looked up in the default context instead, which is where
+    /// the framework and embedded assemblies live. Those can't be unloaded or reloaded.
+    /// `entry_class` overrides the `NTEntrypointSpecifier` attribute.
+    pub fn load_plugin(
+        &self,
+        main_thread: &MainThread,
+        path: Option<&str>,
+        entry_class: Option<&str>,
+    ) -> Result<PluginId, DotnetError> {
+        let path_wide = path.map(U16CString::from_str_truncate);
+        let entry_class_wide = entry_class.map(U16CString::from_str_truncate);
+        let mut plugin_id = -1;
+        catch_managed("LoadPlugin", |error| {
+            let path_ptr = path_wide.as_ref().map_or(std::ptr::null(), |path| path.as_ptr());
+            let entry_class_ptr = entry_class_wide.as_ref().map_or(std::ptr::null(), |class| class.as_ptr());
+            self.load_plugin_raw(main_thread, path_ptr, entry_class_ptr, &mut plugin_id, error)
+        })?;
+        Ok(plugin_id)
     }
 
+    pub fn unload_plugin(&self, main_thread: &MainThread, plugin_id: PluginId) -> Result<(), DotnetError> {
+        catch_managed("UnloadPlugin", |error| self.unload_plugin_raw(main_thread, plugin_id, error))
+    }
 
-    pub fn setup_and_get_config(&self) -> InitConfig {
-        unsafe {
-            let config_raw = self.get_config_fptr.call::<GetConfigFn>(());
-            config_raw.cook(|ptr| {
-                self.free_hstr_fptr.call::<FreeHstrFn>((ptr));
-            })
-        }
+    pub fn plugin_config(&self, main_thread: &MainThread, plugin_id: PluginId) -> Result<InitConfig, DotnetError> {
+        let mut config_raw = RawInitConfig::default();
+        catch_managed("GetPluginConfig", |error| {
+            self.plugin_config_raw(main_thread, plugin_id, &mut config_raw as *mut RawInitConfig, error)
+        })?;
+        // only fails if the context closed in between, the strings leak then
+        Ok(config_raw.cook(|ptr| {
+            let _ = self.free_hstr(ptr);
+        }))
     }
 
-    pub fn initialize<'a>(&self) {
-        unsafe {
-            self.initialize_fptr.call::<InitializeFn>(())
-        }
+    pub fn initialize(&self, main_thread: &MainThread, plugin_id: PluginId) -> Result<(), DotnetError> {
+        catch_managed("Initialize", |error| self.initialize_raw(main_thread, plugin_id, error))
     }
 
-    pub fn shutdown(&self) {
-        unsafe {
-            self.shutdown_fptr.call::<ShutdownFn>(());
-        }
+    pub fn shutdown(&self, main_thread: &MainThread, plugin_id: PluginId) -> Result<(), DotnetError> {
+        catch_managed("Shutdown", |error| self.shutdown_raw(main_thread, plugin_id, error))
     }
 
-    pub fn update(&self, delta_time: f64) {
-        unsafe {
-            self.update_fptr.call::<UpdateFn>((delta_time));
-        }
+    pub fn update(&self, main_thread: &MainThread, plugin_id: PluginId, delta_time: f64) -> Result<(), DotnetError> {
+        catch_managed("Update", |error| self.update_raw(main_thread, plugin_id, delta_time, error))
     }
 
-    pub fn draw(&self) {
-        unsafe {
-            self.draw_fptr.call::<DrawFn>(());
-        }
+    pub fn draw(&self, main_thread: &MainThread, plugin_id: PluginId) -> Result<(), DotnetError> {
+        catch_managed("Draw", |error| self.draw_raw(main_thread, plugin_id, error))
     }
 
-    pub fn bind_render_server(&self, server: &mut RenderServer) {
-        unsafe {
-            self.bind_render_server.call::<BindSingletonFn>(transmute(server));
-        }
+    /// Hands managed code the table it calls native functions through, fails on a version mismatch
+    pub fn bind_native_api(&self, main_thread: &MainThread, api: &'static NativeApi) -> Result<(), DotnetError> {
+        catch_managed("BindNativeApi", |error| self.bind_native_api_raw(main_thread, api, error))
     }
 
-    pub fn unbind_render_server(&self) {
-        unsafe {
-            self.unbind_render_server.call::<UnbindSingletonFn>(());
-        }
+    pub fn bind_render_server(&self, main_thread: &MainThread, server: &mut RenderServer) -> Result<(), DotnetError> {
+        catch_managed("BindRenderServer", |error| {
+            self.bind_render_server_raw(main_thread, server as *mut RenderServer as *const c_void, error)
+        })
     }
 
+    pub fn unbind_render_server(&self, main_thread: &MainThread) -> Result<(), DotnetError> {
+        catch_managed("UnbindRenderServer", |error| self.unbind_render_server_raw(main_thread, error))
+    }
 }
-
@@ -0,0 +1,129 @@
+use std::cell::RefCell;
+
+use crate::{dotnet::DotnetError, runtime_interface::config::ManagedErrorPolicy};
+
+
+const MESSAGE_CAPACITY: usize = 4 * 1024;
+const STACK_TRACE_CAPACITY: usize = 32 * 1024;
+
+pub const MANAGED_STATUS_OK: i32 = 0;
+pub const MANAGED_STATUS_EXCEPTION: i32 = 1;
+
+/// Where a managed lifecycle call writes a caught exception, mirrored by `NativeErrorBuffer` in C#
+///
+/// Both strings are UTF-8 and truncated to their capacity, lengths are in bytes.
+#[repr(C)]
+pub struct RawManagedError {
+    pub message: *mut u8,
+    pub message_capacity: usize,
+    pub message_len: usize,
+    pub stack_trace: *mut u8,
+    pub stack_trace_capacity: usize,
+    pub stack_trace_len: usize,
+}
+
+struct ManagedErrorBuffer {
+    message: Box<[u8]>,
+    stack_trace: Box<[u8]>,
+}
+
+// lifecycle calls happen every frame, so the buffers are allocated once per thread
+thread_local! {
+    static MANAGED_ERROR_BUFFER: RefCell<ManagedErrorBuffer> = RefCell::new(ManagedErrorBuffer {
+        message: vec![0; MESSAGE_CAPACITY].into_boxed_slice(),
+        stack_trace: vec![0; STACK_TRACE_CAPACITY].into_boxed_slice(),
+    });
+}
+
+/// Runs a managed call that reports exceptions through a `RawManagedError` and returns a status
+///
+/// `call` fails without a status if it never reached managed code, see `DotnetFunctionPtr::try_call`.
+pub fn catch_managed<F>(method: &'static str, call: F) -> Result<(), DotnetError>
+    where F: FnOnce(*mut RawManagedError) -> Result<i32, DotnetError>
+{
+    MANAGED_ERROR_BUFFER.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+        let mut raw = RawManagedError {
+            message: buffer.message.as_mut_ptr(),
+            message_capacity: buffer.message.len(),
+            message_len: 0,
+            stack_trace: buffer.stack_trace.as_mut_ptr(),
+            stack_trace_capacity: buffer.stack_trace.len(),
+            stack_trace_len: 0,
+        };
+
+        let status = call(&mut raw)?;
+        if status == MANAGED_STATUS_OK {
+            return Ok(());
+        }
+
+        // truncation can split a code point, hence lossy
+        let message_len = raw.message_len.min(buffer.message.len());
+        let stack_trace_len = raw.stack_trace_len.min(buffer.stack_trace.len());
+        let message = match String::from_utf8_lossy(&buffer.message[..message_len]).into_owned() {
+            message if message.is_empty() => format!("unknown managed error (status {})", status),
+            message => message,
+        };
+        Err(DotnetError::ManagedException {
+            method,
+            message,
+            stack_trace: String::from_utf8_lossy(&buffer.stack_trace[..stack_trace_len]).into_owned(),
+        })
+    })
+}
+
+/// Tracks the latest exception from a managed lifecycle call and applies the error policy
+pub struct ManagedErrorState {
+    policy: ManagedErrorPolicy,
+    error: Option<DotnetError>,
+    repeats: u32,
+}
+
+impl ManagedErrorState {
+    pub fn new(policy: ManagedErrorPolicy) -> Self {
+        Self { policy, error: None, repeats: 0 }
+    }
+
+    pub fn check(&mut self, result: Result<(), DotnetError>) {
+        let Err(error) = result else {
+            return;
+        };
+
+        // under Overlay the same exception usually comes back every frame, only log it once
+        let message = error.to_string();
+        if self.error.as_ref().is_some_and(|last| last.to_string() == message) {
+            self.repeats += 1;
+            return;
+        }
+        println!("Managed exception:\n{}", message);
+        self.error = Some(error);
+        self.repeats = 1;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.policy == ManagedErrorPolicy::Pause && self.error.is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.error = None;
+        self.repeats = 0;
+    }
+
+    pub fn draw_overlay(&self, ui: &imgui::Ui, title: &str) {
+        if self.policy != ManagedErrorPolicy::Overlay {
+            return;
+        }
+        let Some(DotnetError::ManagedException { method, message, stack_trace }) = &self.error else {
+            return;
+        };
+
+        ui.window(title)
+            .size([640.0, 320.0], imgui::Condition::FirstUseEver)
+            .build(|| {
+                ui.text_colored([1.0, 0.4, 0.4, 1.0], format!("{} threw ({}x)", method, self.repeats));
+                ui.text_wrapped(message);
+                ui.separator();
+                ui.text_wrapped(stack_trace);
+            });
+    }
+}
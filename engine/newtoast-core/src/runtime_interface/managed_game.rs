@@ -0,0 +1,64 @@
+use crate::{dotnet::{DotnetError, MainThread}, game::Game, render::RenderServer, runtime_interface::{config::InitConfig, entrypoint::RuntimeEntrypoints, plugin::PluginHost}};
+
+
+/// Every loaded plugin as one `Game`, driven through `RuntimeEntrypoints`
+///
+/// Keeps the main thread token, so it stays on the thread that created the .NET context. The
+/// render server is bound for the managed side from `begin_frame` to `end_frame`.
+pub struct ManagedGame {
+    main_thread: MainThread,
+    runtime_interface: RuntimeEntrypoints,
+    plugins: PluginHost,
+}
+
+impl ManagedGame {
+    pub fn new(main_thread: MainThread, runtime_interface: RuntimeEntrypoints, plugins: PluginHost) -> Self {
+        Self {
+            main_thread,
+            runtime_interface,
+            plugins,
+        }
+    }
+}
+
+impl Game for ManagedGame {
+    type Error = DotnetError;
+
+    fn init_config(&mut self) -> Result<InitConfig, DotnetError> {
+        self.plugins.init_config(&self.main_thread, &self.runtime_interface)
+    }
+
+    fn initialize(&mut self, _render_server: &mut RenderServer) {
+        self.plugins.initialize(&self.main_thread, &self.runtime_interface);
+    }
+
+    fn update(&mut self, _render_server: &mut RenderServer, delta_time: f64) {
+        self.plugins.update(&self.main_thread, &self.runtime_interface, delta_time);
+    }
+
+    fn draw(&mut self, _render_server: &mut RenderServer) {
+        self.plugins.draw(&self.main_thread, &self.runtime_interface);
+    }
+
+    fn shutdown(&mut self) {
+        self.plugins.shutdown(&self.main_thread, &self.runtime_interface);
+    }
+
+    fn begin_frame(&mut self, render_server: &mut RenderServer) {
+        self.plugins.poll_hot_reload(&self.main_thread, &self.runtime_interface);
+
+        if let Err(e) = self.runtime_interface.bind_render_server(&self.main_thread, render_server) {
+            println!("{}", e);
+        }
+    }
+
+    fn end_frame(&mut self, _render_server: &mut RenderServer) {
+        if let Err(e) = self.runtime_interface.unbind_render_server(&self.main_thread) {
+            println!("{}", e);
+        }
+    }
+
+    fn debug_ui(&mut self, ui: &imgui::Ui) {
+        self.plugins.draw_error_overlays(ui);
+    }
+}
@@ -1,25 +1,46 @@
 use std::os::raw::c_void;
 
 pub mod config;
+#[cfg(feature = "dotnet")]
+pub mod csharp_interop;
+#[cfg(feature = "dotnet")]
 pub mod entrypoint;
 pub mod input;
+#[cfg(feature = "dotnet")]
+pub mod managed_error;
+#[cfg(feature = "dotnet")]
+pub mod managed_game;
+#[cfg(feature = "dotnet")]
+pub mod native_api;
+pub mod native_exports;
+#[cfg(feature = "dotnet")]
+pub mod plugin;
+#[cfg(feature = "dotnet")]
 pub mod render;
 pub mod singletons;
+#[cfg(feature = "dotnet")]
+pub mod string_name;
 
+#[cfg(feature = "dotnet")]
+use native_exports::native_exports;
 
-#[no_mangle]
-pub extern "C" fn test_internal_ptr_return_call() -> *const i32 {
-    unsafe {
-        let byte_ptr = std::alloc::alloc(std::alloc::Layout::new::<i32>());
-        let ptr:*mut i32 = std::mem::transmute(byte_ptr);
-        *ptr = 42;
-        ptr
-    }
-}
 
+#[cfg(feature = "dotnet")]
+native_exports! {
+    /// Interop smoke tests
+    pub struct TestApi in "Test" {
+        fn internal_ptr_return_call() -> *const i32 {
+            unsafe {
+                let byte_ptr = std::alloc::alloc(std::alloc::Layout::new::<i32>());
+                let ptr:*mut i32 = std::mem::transmute(byte_ptr);
+                *ptr = 42;
+                ptr
+            }
+        }
 
-#[no_mangle]
-pub extern "C" fn test_modify_ref(u8_ref: &mut u8) {
-    *u8_ref *= 2;
-    println!("u8 ref after modification: {}", u8_ref);
-}
\ No newline at end of file
+        fn modify_ref(u8_ref: &mut u8) {
+            *u8_ref *= 2;
+            println!("u8 ref after modification: {}", u8_ref);
+        }
+    }
+}
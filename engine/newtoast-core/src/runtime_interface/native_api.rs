@@ -0,0 +1,57 @@
+use crate::{render::math_types::{Color, Transform2D}, runtime_interface::{native_exports::{NativeStruct, NativeTable}, render::{RenderServerApi, ShaderParameterInfo}, string_name::StringNameApi, TestApi}};
+
+
+/// Bumped on any change to the layout of `NativeApi` or one of its tables
+///
+/// That includes appending, the tables are nested so a new entry moves every table after it.
+pub const NATIVE_API_VERSION: u32 = 10;
+
+/// Every `native_struct!` passed through `NativeApi`
+pub const NATIVE_API_STRUCTS: &[NativeStruct] = &[
+    Transform2D::CS_STRUCT,
+    Color::CS_STRUCT,
+    ShaderParameterInfo::CS_STRUCT,
+];
+
+macro_rules! native_api {
+    (
+        $(#[$struct_meta:meta])*
+        pub struct NativeApi {
+            $($field:ident: $table:ident,)*
+        }
+    ) => {
+        $(#[$struct_meta])*
+        #[repr(C)]
+        pub struct NativeApi {
+            pub version: u32,
+            pub size: u32,
+            $(pub $field: $table,)*
+        }
+
+        pub static NATIVE_API: NativeApi = NativeApi {
+            version: NATIVE_API_VERSION,
+            size: std::mem::size_of::<NativeApi>() as u32,
+            $($field: $table::TABLE,)*
+        };
+
+        /// Every table in `NativeApi`, in field order
+        pub const NATIVE_API_TABLES: &[NativeTable] = &[$($table::EXPORTS,)*];
+    };
+}
+
+native_api! {
+    /// Every native function managed code can call, generated into `NativeApi` in C#
+    ///
+    /// Handed over once by `RuntimeEntrypoints::bind_native_api` before any plugin loads. Managed
+    /// code calls through these pointers instead of resolving exports from a library by name, so
+    /// it always talks to the executable that is hosting it. Managed refuses the table unless
+    /// `version` and `size` match what it was built against.
+    ///
+    /// Each subsystem declares its table next to its exports with `native_exports!`, a new
+    /// subsystem adds its table here and regenerates the C# side, see `csharp_interop`.
+    pub struct NativeApi {
+        string_name: StringNameApi,
+        render_server: RenderServerApi,
+        test: TestApi,
+    }
+}
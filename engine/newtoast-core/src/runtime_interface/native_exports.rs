@@ -0,0 +1,242 @@
+use std::os::raw::c_void;
+
+use glam::{Vec2, Vec3};
+
+
+/// How a Rust FFI type is spelled on the managed side
+///
+/// Opaque types only ever cross behind a pointer, they override `cs_pointer_name` to `nint`.
+pub trait CsType {
+    fn cs_name() -> String;
+
+    fn cs_pointer_name() -> String {
+        format!("{}*", Self::cs_name())
+    }
+}
+
+macro_rules! cs_primitive {
+    ($($ty:ty => $cs_name:literal),* $(,)?) => {
+        $(impl CsType for $ty {
+            fn cs_name() -> String {
+                $cs_name.into()
+            }
+        })*
+    };
+}
+
+cs_primitive! {
+    u8 => "byte",
+    i8 => "sbyte",
+    u16 => "ushort",
+    i16 => "short",
+    u32 => "uint",
+    i32 => "int",
+    u64 => "ulong",
+    i64 => "long",
+    usize => "nuint",
+    isize => "nint",
+    f32 => "float",
+    f64 => "double",
+}
+
+// GlmSharp, which the framework ships, lays these out as plain floats too. glam's Vec4 is
+// 16-byte aligned on SIMD targets, so it's left out
+cs_primitive! {
+    Vec2 => "vec2",
+    Vec3 => "vec3",
+}
+
+impl CsType for c_void {
+    fn cs_name() -> String {
+        "void".into()
+    }
+
+    fn cs_pointer_name() -> String {
+        "nint".into()
+    }
+}
+
+impl<T: CsType + ?Sized> CsType for *const T {
+    fn cs_name() -> String {
+        T::cs_pointer_name()
+    }
+}
+
+impl<T: CsType + ?Sized> CsType for *mut T {
+    fn cs_name() -> String {
+        T::cs_pointer_name()
+    }
+}
+
+impl<T: CsType + ?Sized> CsType for &T {
+    fn cs_name() -> String {
+        T::cs_pointer_name()
+    }
+}
+
+impl<T: CsType + ?Sized> CsType for &mut T {
+    fn cs_name() -> String {
+        T::cs_pointer_name()
+    }
+}
+
+/// A parameter, or a field of a `native_struct!`
+pub struct NativeParam {
+    pub name: &'static str,
+    pub cs_type: fn() -> String,
+}
+
+pub struct NativeExport {
+    pub name: &'static str,
+    pub doc: &'static [&'static str],
+    pub params: &'static [NativeParam],
+    pub ret: Option<fn() -> String>,
+    /// Gets a public wrapper that passes the bound receiver as the first argument
+    pub bound: bool,
+}
+
+/// A `native_struct!`, generated into a `partial struct` in C#
+pub struct NativeStruct {
+    pub name: &'static str,
+    pub fields: &'static [NativeParam],
+}
+
+/// Everything `csharp_interop` needs to know about one table of `NativeApi`
+pub struct NativeTable {
+    pub name: &'static str,
+    pub functions: &'static [NativeExport],
+}
+
+/// Declares a subsystem's table of native functions for `NativeApi`
+///
+/// ```ignore
+/// native_exports! {
+///     pub struct AudioApi in "Audio" {
+///         /// Returns the voice id
+///         @bound fn play(audio: &mut Audio, sound: Id) -> Id {
+///             audio.play(sound)
+///         }
+///         fn master_volume(audio: &mut Audio) -> f32 {
+///             audio.master_volume()
+///         }
+///     }
+/// }
+/// ```
+///
+/// Generates the `repr(C)` struct of function pointers, an `extern "C"` associated function per
+/// entry, `TABLE` pointing at those and `EXPORTS` describing them to `csharp_interop`. On the
+/// managed side the table becomes `AudioApi`, every entry gets a forwarding method on
+/// `AudioInternal` and `@bound` entries also get a public one on the partial class `Audio`,
+/// which has to provide `BoundAudio` and `IsAudioBound` for the first argument.
+///
+/// Only doc comments are allowed on entries, they're carried over to C#. Every argument and
+/// return type needs a `CsType`.
+#[cfg(feature = "dotnet")]
+macro_rules! native_exports {
+    (
+        $(#[$struct_meta:meta])*
+        $struct_vis:vis struct $name:ident in $cs_name:literal {
+            $(
+                $(#[doc = $fn_doc:literal])*
+                $(@$marker:ident)? fn $fn_name:ident ( $($arg:ident : $arg_ty:ty),* $(,)? ) $(-> $ret:ty)? $body:block
+            )*
+        }
+    ) => {
+        $(#[$struct_meta])*
+        #[repr(C)]
+        $struct_vis struct $name {
+            $(pub $fn_name: extern "C" fn($($arg_ty),*) $(-> $ret)?,)*
+        }
+
+        impl $name {
+            $(
+                $(#[doc = $fn_doc])*
+                extern "C" fn $fn_name($($arg: $arg_ty),*) $(-> $ret)? $body
+            )*
+
+            pub const TABLE: Self = Self {
+                $($fn_name: Self::$fn_name,)*
+            };
+
+            pub const EXPORTS: $crate::runtime_interface::native_exports::NativeTable = $crate::runtime_interface::native_exports::NativeTable {
+                name: $cs_name,
+                functions: &[
+                    $($crate::runtime_interface::native_exports::NativeExport {
+                        name: stringify!($fn_name),
+                        doc: &[$($fn_doc),*],
+                        params: &[
+                            $($crate::runtime_interface::native_exports::NativeParam {
+                                name: stringify!($arg),
+                                cs_type: <$arg_ty as $crate::runtime_interface::native_exports::CsType>::cs_name,
+                            },)*
+                        ],
+                        ret: $crate::runtime_interface::native_exports::native_exports!(@ret $($ret)?),
+                        bound: $crate::runtime_interface::native_exports::native_exports!(@bound $($marker)?),
+                    },)*
+                ],
+            };
+        }
+    };
+
+    (@ret) => { None };
+    (@ret $ret:ty) => { Some(<$ret as $crate::runtime_interface::native_exports::CsType>::cs_name) };
+
+    (@bound) => { false };
+    (@bound bound) => { true };
+}
+
+#[cfg(feature = "dotnet")]
+pub(crate) use native_exports;
+
+/// Declares a `repr(C)` struct that is passed by value through `NativeApi`
+///
+/// ```ignore
+/// native_struct! {
+///     #[derive(Copy, Clone)]
+///     pub struct Rect in "Rect" {
+///         pub min: Vec2,
+///         pub max: Vec2,
+///     }
+/// }
+/// ```
+///
+/// Generates the struct, its `CsType` and `CS_STRUCT` for `csharp_interop`, which emits a
+/// sequential `partial struct Rect` with the fields in order. Hand-written C# adds the methods.
+/// The struct also has to be listed in `NATIVE_API_STRUCTS`.
+macro_rules! native_struct {
+    (
+        $(#[$struct_meta:meta])*
+        $struct_vis:vis struct $name:ident in $cs_name:literal {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $field:ident : $field_ty:ty
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$struct_meta])*
+        #[repr(C)]
+        $struct_vis struct $name {
+            $($(#[$field_meta])* $field_vis $field: $field_ty,)*
+        }
+
+        impl $crate::runtime_interface::native_exports::CsType for $name {
+            fn cs_name() -> String {
+                $cs_name.into()
+            }
+        }
+
+        impl $name {
+            pub const CS_STRUCT: $crate::runtime_interface::native_exports::NativeStruct = $crate::runtime_interface::native_exports::NativeStruct {
+                name: $cs_name,
+                fields: &[
+                    $($crate::runtime_interface::native_exports::NativeParam {
+                        name: stringify!($field),
+                        cs_type: <$field_ty as $crate::runtime_interface::native_exports::CsType>::cs_name,
+                    },)*
+                ],
+            };
+        }
+    };
+}
+
+pub(crate) use native_struct;
@@ -0,0 +1,207 @@
+use std::{path::{Path, PathBuf}, time::Duration};
+
+use crate::{dotnet::{DotnetError, MainThread}, runtime_interface::{config::{InitConfig, ManagedErrorPolicy}, entrypoint::{PluginId, RuntimeEntrypoints}, managed_error::ManagedErrorState}, util::file_watcher::FileWatcher};
+
+
+/// One managed entrypoint: the game itself or a mod
+///
+/// A plugin whose load or lifecycle calls fail is kept around, it just stops getting called
+/// (or keeps going, under `ManagedErrorPolicy::Overlay`) until a rebuild of its assembly loads.
+pub struct Plugin {
+    name: String,
+    path: Option<PathBuf>,
+    entry_class: Option<String>,
+    id: Option<PluginId>,
+    watcher: Option<FileWatcher>,
+    errors: ManagedErrorState,
+}
+
+impl Plugin {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.id.is_some()
+    }
+
+    fn is_running(&self) -> bool {
+        self.is_loaded() && !self.errors.is_paused()
+    }
+}
+
+/// Every plugin the engine drives, in load order
+///
+/// Lifecycle calls go out in that order each frame. An exception or load failure in one plugin
+/// is recorded on that plugin only.
+pub struct PluginHost {
+    plugins: Vec<Plugin>,
+    policy: ManagedErrorPolicy,
+}
+
+impl PluginHost {
+    pub fn new(policy: ManagedErrorPolicy) -> Self {
+        Self {
+            plugins: Vec::new(),
+            policy,
+        }
+    }
+
+    pub fn plugins(&self) -> &[Plugin] {
+        &self.plugins
+    }
+
+    /// Loads and registers a plugin, without a path it binds the entrypoint in the default context
+    ///
+    /// With a `hot_reload_settle_time` the assembly is watched and reloaded whenever it's rebuilt.
+    /// A failed load is reported like any other managed error and the plugin stays registered,
+    /// so a watched plugin gets another try on its next build.
+    pub fn load(
+        &mut self,
+        main_thread: &MainThread,
+        runtime_interface: &RuntimeEntrypoints,
+        name: &str,
+        path: Option<&Path>,
+        entry_class: Option<&str>,
+        hot_reload_settle_time: Option<Duration>,
+    ) -> Result<(), DotnetError> {
+        let mut plugin = Plugin {
+            name: name.to_owned(),
+            path: path.map(Path::to_path_buf),
+            entry_class: entry_class.map(str::to_owned),
+            id: None,
+            watcher: path.zip(hot_reload_settle_time).map(|(path, settle)| FileWatcher::new(path, settle)),
+            errors: ManagedErrorState::new(self.policy),
+        };
+
+        let path_str = path.map(|path| path.to_string_lossy().into_owned());
+        let result = runtime_interface.load_plugin(main_thread, path_str.as_deref(), entry_class)
+            .map(|id| plugin.id = Some(id));
+        plugin.errors.check(result.clone());
+
+        self.plugins.push(plugin);
+        result
+    }
+
+    /// Window settings come from the first plugin that loaded
+    pub fn init_config(&self, main_thread: &MainThread, runtime_interface: &RuntimeEntrypoints) -> Result<InitConfig, DotnetError> {
+        match self.plugins.iter().find_map(|plugin| plugin.id) {
+            Some(id) => runtime_interface.plugin_config(main_thread, id),
+            None => Ok(InitConfig::default()),
+        }
+    }
+
+    pub fn initialize(&mut self, main_thread: &MainThread, runtime_interface: &RuntimeEntrypoints) {
+        for plugin in &mut self.plugins {
+            if let Some(id) = plugin.id {
+                plugin.errors.check(runtime_interface.initialize(main_thread, id));
+            }
+        }
+    }
+
+    pub fn update(&mut self, main_thread: &MainThread, runtime_interface: &RuntimeEntrypoints, delta_time: f64) {
+        for plugin in &mut self.plugins {
+            if let (true, Some(id)) = (plugin.is_running(), plugin.id) {
+                plugin.errors.check(runtime_interface.update(main_thread, id, delta_time));
+            }
+        }
+    }
+
+    pub fn draw(&mut self, main_thread: &MainThread, runtime_interface: &RuntimeEntrypoints) {
+        for plugin in &mut self.plugins {
+            if let (true, Some(id)) = (plugin.is_running(), plugin.id) {
+                plugin.errors.check(runtime_interface.draw(main_thread, id));
+            }
+        }
+    }
+
+    /// Shuts down and unloads every plugin, in reverse load order
+    pub fn shutdown(&mut self, main_thread: &MainThread, runtime_interface: &RuntimeEntrypoints) {
+        for plugin in self.plugins.iter_mut().rev() {
+            if let Some(id) = plugin.id.take() {
+                if let Err(e) = runtime_interface.shutdown(main_thread, id) {
+                    println!("{}: {}", plugin.name, e);
+                }
+                if let Err(e) = runtime_interface.unload_plugin(main_thread, id) {
+                    println!("{}: {}", plugin.name, e);
+                }
+            }
+        }
+    }
+
+    /// Reloads every watched plugin whose assembly was rebuilt
+    pub fn poll_hot_reload(&mut self, main_thread: &MainThread, runtime_interface: &RuntimeEntrypoints) {
+        for plugin in &mut self.plugins {
+            let rebuilt = plugin.watcher.as_mut().is_some_and(|watcher| watcher.poll());
+            if !rebuilt {
+                continue;
+            }
+
+            // on a failed load the plugin stays unloaded until the next successful build
+            let result = hot_reload_plugin(main_thread, runtime_interface, plugin);
+            plugin.errors.clear();
+            plugin.errors.check(result);
+        }
+    }
+
+    pub fn draw_error_overlays(&self, ui: &imgui::Ui) {
+        for plugin in &self.plugins {
+            plugin.errors.draw_overlay(ui, &format!("Managed exception in {}", plugin.name));
+        }
+    }
+}
+
+/// Swaps in a rebuilt plugin assembly while the window, GL context and RenderServer stay alive
+fn hot_reload_plugin(
+    main_thread: &MainThread,
+    runtime_interface: &RuntimeEntrypoints,
+    plugin: &mut Plugin,
+) -> Result<(), DotnetError> {
+    let Some(path) = plugin.path.as_ref() else {
+        return Ok(());
+    };
+    println!("Reloading {}:\n  {}", plugin.name, path.display());
+
+    if let Some(id) = plugin.id.take() {
+        // a throwing Shutdown is exactly what a reload is meant to fix, so keep going
+        if let Err(e) = runtime_interface.shutdown(main_thread, id) {
+            println!("{}", e);
+        }
+        if let Err(e) = runtime_interface.unload_plugin(main_thread, id) {
+            println!("{}", e);
+        }
+    }
+
+    let id = runtime_interface.load_plugin(main_thread, Some(&path.to_string_lossy()), plugin.entry_class.as_deref())?;
+    plugin.id = Some(id);
+
+    // window settings only apply at startup, so the new entrypoint's config is ignored
+    runtime_interface.initialize(main_thread, id)
+}
+
+/// Finds mod assemblies, one per subdirectory as `<dir>/<name>/<name>.dll`, ordered by name
+///
+/// Dependencies of a mod sit next to its assembly and are resolved from there by its load context.
+pub fn discover_mods(mods_directory: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(mods_directory) else {
+        return Vec::new();
+    };
+
+    let mut mods: Vec<(String, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let assembly = entry.path().join(format!("{}.dll", name));
+            if assembly.is_file() {
+                Some((name, assembly))
+            } else {
+                println!("Skipping mod directory {}, it has no {}.dll", entry.path().display(), name);
+                None
+            }
+        })
+        .collect();
+
+    mods.sort_by(|(a, _), (b, _)| a.cmp(b));
+    mods
+}
@@ -1,62 +1,413 @@
-use crate::render::{Id, RenderServer, Transform2D};
+use std::os::raw::c_void;
 
+use crate::{
+    dotnet::ManagedHandle,
+    render::{
+        math_types::{Color, Mat3, Mat4, Transform2D, Vec2, Vec3, Vec4},
+        Id, Mesh2DData, RenderServer, ShaderParameter, ShaderValueType, UniformBinding,
+    },
+    runtime_interface::native_exports::{native_exports, native_struct, CsType},
+    util::string_name::StringName,
+};
 
-#[no_mangle]
-extern "C" fn render_server_renderable_2d_create(render_server: &mut RenderServer) -> Id {
-    render_server.renderable_2d_create()
-}
 
-#[no_mangle]
-extern "C" fn render_server_renderable_2d_destroy(render_server: &mut RenderServer, id: Id) {
-    render_server.renderable_2d_destroy(id)
+impl CsType for RenderServer {
+    fn cs_name() -> String {
+        "nint".into()
+    }
+
+    fn cs_pointer_name() -> String {
+        "nint".into()
+    }
 }
 
-#[no_mangle]
-extern "C" fn render_server_renderable_2d_set_transform(render_server: &mut RenderServer, id: Id, transform: Transform2D) {
-    render_server.renderable_2d_set_transform(id, transform)
+// hand-written as a C# enum with the same values
+impl CsType for ShaderValueType {
+    fn cs_name() -> String {
+        "ShaderValueType".into()
+    }
 }
 
-#[no_mangle]
-extern "C" fn render_server_renderable_2d_set_target(render_server: &mut RenderServer, id: Id) -> Id {
-    render_server.renderable_2d_set_target(id)
+native_struct! {
+    /// A reflected shader uniform or attribute
+    #[derive(Copy, Clone, Debug)]
+    pub struct ShaderParameterInfo in "ShaderParameterInfo" {
+        pub name: StringName,
+        pub value_type: ShaderValueType,
+        pub array_len: i32,
+    }
 }
 
-#[no_mangle]
-extern "C" fn render_server_renderable_2d_set_target_with_target(render_server: &mut RenderServer, id: Id, target: Id) {
-    render_server.renderable_2d_set_target_with_target(id, target)
+impl From<&ShaderParameter> for ShaderParameterInfo {
+    fn from(parameter: &ShaderParameter) -> Self {
+        Self {
+            name: parameter.name,
+            value_type: parameter.value_type,
+            array_len: parameter.array_len,
+        }
+    }
 }
 
-#[no_mangle]
-extern "C" fn render_server_material_2d_create(render_server: &mut RenderServer) -> Id {
-    render_server.material_2d_create()
+native_exports! {
+    /// The render server's slice of `NativeApi`
+    pub struct RenderServerApi in "RenderServer" {
+        @bound fn renderable_2d_create(render_server: &mut RenderServer) -> Id {
+            render_server.renderable_2d_create()
+        }
+
+        @bound fn renderable_2d_destroy(render_server: &mut RenderServer, id: Id) {
+            render_server.renderable_2d_destroy(id)
+        }
+
+        @bound fn renderable_2d_set_transform(render_server: &mut RenderServer, id: Id, transform: Transform2D) {
+            render_server.renderable_2d_set_transform(id, transform)
+        }
+
+        @bound fn renderable_2d_get_transform(render_server: &mut RenderServer, id: Id) -> Transform2D {
+            render_server.renderable_2d_get_transform(id)
+        }
+
+        /// Takes ownership of `user_data`, a GCHandle or null
+        ///
+        /// Returns 0 if the renderable doesn't exist, the caller then still owns `user_data`.
+        fn renderable_2d_set_user_data(render_server: &mut RenderServer, id: Id, user_data: *mut c_void) -> u8 {
+            let user_data = unsafe { ManagedHandle::from_raw(user_data) };
+            match render_server.renderable_2d_set_user_data(id, user_data) {
+                Ok(()) => 1,
+                Err(user_data) => {
+                    // ownership goes back to the caller
+                    if let Some(user_data) = user_data {
+                        user_data.into_raw();
+                    }
+                    0
+                },
+            }
+        }
+
+        /// The handle stays owned by the renderable, don't free it
+        fn renderable_2d_get_user_data(render_server: &mut RenderServer, id: Id) -> *mut c_void {
+            render_server.renderable_2d_get_user_data(id).map_or(std::ptr::null_mut(), ManagedHandle::as_raw)
+        }
+
+        @bound fn material_2d_create(render_server: &mut RenderServer) -> Id {
+            render_server.material_2d_create()
+        }
+
+        @bound fn material_2d_destroy(render_server: &mut RenderServer, id: Id) {
+            render_server.material_2d_destroy(id)
+        }
+
+        /// Returns 0 if the material or shader doesn't exist, with the reason written to
+        /// `error_log` like `shader_2d_create`
+        ///
+        /// Parameters the new shader doesn't declare with the same type are dropped.
+        fn material_2d_set_shader(
+            render_server: &mut RenderServer, id: Id, shader: Id,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> u8 {
+            match render_server.material_2d_set_shader(id, shader) {
+                Ok(()) => {
+                    unsafe { set_error_log_len(error_log_len, 0) };
+                    1
+                },
+                Err(e) => {
+                    unsafe { write_error_log(&e, error_log, error_log_capacity, error_log_len) };
+                    0
+                },
+            }
+        }
+
+        /// The sources are UTF-8 and don't need a terminator
+        ///
+        /// Returns 0 on failure, with the compiler or linker log written to `error_log` as UTF-8,
+        /// truncated to `error_log_capacity` bytes.
+        fn shader_2d_create(
+            render_server: &mut RenderServer,
+            vertex_src: *const u8, vertex_len: usize,
+            fragment_src: *const u8, fragment_len: usize,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> Id {
+            let vertex_src = unsafe { utf8_lossy(vertex_src, vertex_len) };
+            let fragment_src = unsafe { utf8_lossy(fragment_src, fragment_len) };
+            unsafe { set_error_log_len(error_log_len, 0) };
+            match render_server.shader_2d_create(&vertex_src, &fragment_src) {
+                Ok(id) => id,
+                Err(e) => {
+                    unsafe { write_error_log(&e, error_log, error_log_capacity, error_log_len) };
+                    0
+                },
+            }
+        }
+
+        @bound fn shader_2d_destroy(render_server: &mut RenderServer, id: Id) {
+            render_server.shader_2d_destroy(id)
+        }
+
+        /// 0 for a shader that doesn't exist
+        fn shader_2d_get_uniform_count(render_server: &mut RenderServer, id: Id) -> usize {
+            render_server.shader_2d_get_uniforms(id).map_or(0, <[ShaderParameter]>::len)
+        }
+
+        /// Returns 0 and leaves `out_info` alone if the shader or index doesn't exist
+        fn shader_2d_get_uniform(render_server: &mut RenderServer, id: Id, index: usize, out_info: &mut ShaderParameterInfo) -> u8 {
+            let uniforms = render_server.shader_2d_get_uniforms(id).unwrap_or_default();
+            uniforms.get(index).map_or(0, |uniform| {
+                *out_info = uniform.into();
+                1
+            })
+        }
+
+        /// 0 for a shader that doesn't exist
+        fn shader_2d_get_attribute_count(render_server: &mut RenderServer, id: Id) -> usize {
+            render_server.shader_2d_get_attributes(id).map_or(0, <[ShaderParameter]>::len)
+        }
+
+        /// Returns 0 and leaves `out_info` alone if the shader or index doesn't exist
+        fn shader_2d_get_attribute(render_server: &mut RenderServer, id: Id, index: usize, out_info: &mut ShaderParameterInfo) -> u8 {
+            let attributes = render_server.shader_2d_get_attributes(id).unwrap_or_default();
+            attributes.get(index).map_or(0, |attribute| {
+                *out_info = attribute.into();
+                1
+            })
+        }
+
+        /// The `material_2d_set_*` setters return 0 if the material's shader has no active uniform
+        /// `name` of that type, if it's an array or if a `value` pointer is null, with the reason
+        /// written to `error_log` like `shader_2d_create`
+        fn material_2d_set_float(
+            render_server: &mut RenderServer, id: Id, name: StringName, value: f32,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> u8 {
+            unsafe { set_shader_param(render_server, id, name, UniformBinding::Float(value), error_log, error_log_capacity, error_log_len) }
+        }
+
+        fn material_2d_set_int(
+            render_server: &mut RenderServer, id: Id, name: StringName, value: i32,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> u8 {
+            unsafe { set_shader_param(render_server, id, name, UniformBinding::Int(value), error_log, error_log_capacity, error_log_len) }
+        }
+
+        fn material_2d_set_vec2(
+            render_server: &mut RenderServer, id: Id, name: StringName, value: Vec2,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> u8 {
+            unsafe { set_shader_param(render_server, id, name, UniformBinding::Vec2(value), error_log, error_log_capacity, error_log_len) }
+        }
+
+        fn material_2d_set_vec3(
+            render_server: &mut RenderServer, id: Id, name: StringName, value: Vec3,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> u8 {
+            unsafe { set_shader_param(render_server, id, name, UniformBinding::Vec3(value), error_log, error_log_capacity, error_log_len) }
+        }
+
+        /// `value` points to 4 floats, `Vec4` is SIMD-aligned so it isn't passed by value
+        fn material_2d_set_vec4(
+            render_server: &mut RenderServer, id: Id, name: StringName, value: *const f32,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> u8 {
+            if value.is_null() {
+                unsafe { write_error_log(&"value is null", error_log, error_log_capacity, error_log_len) };
+                return 0;
+            }
+            let value = Vec4::from_slice(unsafe { std::slice::from_raw_parts(value, 4) });
+            unsafe { set_shader_param(render_server, id, name, UniformBinding::Vec4(value), error_log, error_log_capacity, error_log_len) }
+        }
+
+        /// `value` points to 9 floats, column-major
+        fn material_2d_set_mat3(
+            render_server: &mut RenderServer, id: Id, name: StringName, value: *const f32,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> u8 {
+            if value.is_null() {
+                unsafe { write_error_log(&"value is null", error_log, error_log_capacity, error_log_len) };
+                return 0;
+            }
+            let value = Mat3::from_cols_slice(unsafe { std::slice::from_raw_parts(value, 9) });
+            unsafe { set_shader_param(render_server, id, name, UniformBinding::Mat3(value), error_log, error_log_capacity, error_log_len) }
+        }
+
+        /// `value` points to 16 floats, column-major
+        fn material_2d_set_mat4(
+            render_server: &mut RenderServer, id: Id, name: StringName, value: *const f32,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> u8 {
+            if value.is_null() {
+                unsafe { write_error_log(&"value is null", error_log, error_log_capacity, error_log_len) };
+                return 0;
+            }
+            let value = Mat4::from_cols_slice(unsafe { std::slice::from_raw_parts(value, 16) });
+            unsafe { set_shader_param(render_server, id, name, UniformBinding::Mat4(value), error_log, error_log_capacity, error_log_len) }
+        }
+
+        /// Sets a `vec4` uniform
+        fn material_2d_set_color(
+            render_server: &mut RenderServer, id: Id, name: StringName, value: Color,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> u8 {
+            unsafe { set_shader_param(render_server, id, name, UniformBinding::Color(value), error_log, error_log_capacity, error_log_len) }
+        }
+
+        /// Points a `sampler2D` uniform at texture unit `slot`
+        fn material_2d_set_texture_slot(
+            render_server: &mut RenderServer, id: Id, name: StringName, slot: i32,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> u8 {
+            unsafe { set_shader_param(render_server, id, name, UniformBinding::TextureSlot(slot), error_log, error_log_capacity, error_log_len) }
+        }
+
+        /// Surfaces draw in the order they're added, returns 0 if the renderable doesn't exist
+        ///
+        /// The material's shader gets the renderable's transform in `u_transform` if it declares it.
+        @bound fn renderable_2d_add_surface(render_server: &mut RenderServer, renderable: Id, material: Id, mesh: Id) -> u8 {
+            render_server.renderable_2d_add_surface(renderable, material, mesh) as u8
+        }
+
+        @bound fn renderable_2d_clear_surfaces(render_server: &mut RenderServer, renderable: Id) {
+            render_server.renderable_2d_clear_surfaces(renderable)
+        }
+
+        /// `uvs`, `colors` and `indices` may be null, every non-null array is read before returning
+        ///
+        /// Returns 0 if the mesh couldn't be created, with the reason written to `error_log` like
+        /// `shader_2d_create`.
+        fn mesh_2d_create(
+            render_server: &mut RenderServer,
+            positions: *const Vec2, uvs: *const Vec2, colors: *const Color, vertex_count: usize,
+            indices: *const u32, index_count: usize,
+            dynamic: u8,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> Id {
+            let data = unsafe { mesh_2d_data(positions, uvs, colors, vertex_count, indices, index_count) };
+            unsafe { set_error_log_len(error_log_len, 0) };
+            match render_server.mesh_2d_create(&data, dynamic != 0) {
+                Ok(id) => id,
+                Err(e) => {
+                    unsafe { write_error_log(&e, error_log, error_log_capacity, error_log_len) };
+                    0
+                },
+            }
+        }
+
+        /// Same arrays and error log as `mesh_2d_create`, returns 0 if the update failed
+        fn mesh_2d_update(
+            render_server: &mut RenderServer, id: Id,
+            positions: *const Vec2, uvs: *const Vec2, colors: *const Color, vertex_count: usize,
+            indices: *const u32, index_count: usize,
+            error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+        ) -> u8 {
+            let data = unsafe { mesh_2d_data(positions, uvs, colors, vertex_count, indices, index_count) };
+            unsafe { set_error_log_len(error_log_len, 0) };
+            match render_server.mesh_2d_update(id, &data) {
+                Ok(()) => 1,
+                Err(e) => {
+                    unsafe { write_error_log(&e, error_log, error_log_capacity, error_log_len) };
+                    0
+                },
+            }
+        }
+
+        @bound fn mesh_2d_destroy(render_server: &mut RenderServer, id: Id) {
+            render_server.mesh_2d_destroy(id)
+        }
+    }
 }
 
-#[no_mangle]
-extern "C" fn render_server_material_2d_destroy(render_server: &mut RenderServer, id: Id) {
-    render_server.material_2d_destroy(id)
+
+/// Null pointers are absent arrays, every other pointer must be valid for its count
+unsafe fn mesh_2d_data<'a>(
+    positions: *const Vec2, uvs: *const Vec2, colors: *const Color, vertex_count: usize,
+    indices: *const u32, index_count: usize,
+) -> Mesh2DData<'a> {
+    unsafe fn slice<'a, T>(ptr: *const T, len: usize) -> Option<&'a [T]> {
+        (!ptr.is_null()).then(|| std::slice::from_raw_parts(ptr, len))
+    }
+
+    Mesh2DData {
+        positions: slice(positions, vertex_count).unwrap_or_default(),
+        uvs: slice(uvs, vertex_count),
+        colors: slice(colors, vertex_count),
+        indices: slice(indices, index_count),
+    }
 }
 
-#[no_mangle]
-extern "C" fn render_server_material_2d_set_shader(render_server: &mut RenderServer, id: Id, shader: Id) {
-    render_server.material_2d_set_shader(id, shader)
+/// Null is an empty string, invalid UTF-8 is replaced rather than rejected
+unsafe fn utf8_lossy<'a>(data: *const u8, len: usize) -> std::borrow::Cow<'a, str> {
+    if data.is_null() {
+        return "".into();
+    }
+    String::from_utf8_lossy(std::slice::from_raw_parts(data, len))
 }
 
-// TODO render_server_material_2d_set_shader_param_
-// float, int, ...
+/// Writes `error` as UTF-8, truncated to at most `capacity` bytes without splitting a character
+///
+/// Nothing is written if `log` is null, and the length only if `log_len` isn't null.
+unsafe fn write_error_log(error: &impl std::fmt::Display, log: *mut u8, capacity: usize, log_len: *mut usize) {
+    let mut len = 0;
+    if !log.is_null() {
+        let message = error.to_string();
+        len = message.len().min(capacity);
+        while !message.is_char_boundary(len) {
+            len -= 1;
+        }
+        std::ptr::copy_nonoverlapping(message.as_ptr(), log, len);
+    }
+    set_error_log_len(log_len, len);
+}
 
-#[no_mangle]
-extern "C" fn render_server_renderable_2d_add_surface(render_server: &mut RenderServer, renderable: Id, material: Id, mesh: Id) -> Id {
-    render_server.renderable_2d_add_surface(renderable, material, mesh)
+unsafe fn set_error_log_len(log_len: *mut usize, len: usize) {
+    if !log_len.is_null() {
+        *log_len = len;
+    }
 }
 
-#[no_mangle]
-extern "C" fn render_server_renderable_2d_clear_surfaces(render_server: &mut RenderServer, renderable: Id) {
-    render_server.renderable_2d_clear_surfaces(renderable)
+unsafe fn set_shader_param(
+    render_server: &mut RenderServer, id: Id, name: StringName, binding: UniformBinding,
+    error_log: *mut u8, error_log_capacity: usize, error_log_len: *mut usize,
+) -> u8 {
+    match render_server.material_2d_set_shader_param(id, name, binding) {
+        Ok(()) => {
+            set_error_log_len(error_log_len, 0);
+            1
+        },
+        Err(e) => {
+            write_error_log(&e, error_log, error_log_capacity, error_log_len);
+            0
+        },
+    }
 }
 
-// TODO render_server_mesh_2d_create(render_server: &mut RenderServer, ...) -> Id
 
-#[no_mangle]
-extern "C" fn render_server_mesh_2d_destroy(render_server: &mut RenderServer, id: Id) {
-    render_server.mesh_2d_destroy(id)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_log(message: &str, capacity: usize) -> String {
+        let mut log = vec![0; capacity];
+        let mut len = usize::MAX;
+        unsafe { write_error_log(&message, log.as_mut_ptr(), capacity, &mut len) };
+        log.truncate(len);
+        String::from_utf8(log).unwrap()
+    }
+
+    #[test]
+    fn error_log_truncates_on_a_char_boundary() {
+        assert_eq!(error_log("shader failed", 64), "shader failed");
+        assert_eq!(error_log("shader failed", 6), "shader");
+        // 'é' is two bytes, cutting after the first would leave invalid UTF-8
+        assert_eq!(error_log("café", 4), "caf");
+        assert_eq!(error_log("é", 1), "");
+    }
+
+    #[test]
+    fn error_log_accepts_null_pointers() {
+        let mut len = usize::MAX;
+        unsafe { write_error_log(&"failed", std::ptr::null_mut(), 0, &mut len) };
+        assert_eq!(len, 0);
+
+        let mut log = [0; 8];
+        unsafe { write_error_log(&"failed", log.as_mut_ptr(), log.len(), std::ptr::null_mut()) };
+        assert_eq!(&log[..6], b"failed");
+    }
 }
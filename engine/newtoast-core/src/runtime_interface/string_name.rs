@@ -0,0 +1,46 @@
+use widestring::U16Str;
+
+use crate::{runtime_interface::native_exports::{native_exports, CsType}, util::string_name::StringName};
+
+
+// the managed StringName wraps the raw id itself
+impl CsType for StringName {
+    fn cs_name() -> String {
+        "uint".into()
+    }
+}
+
+native_exports! {
+    /// Interning
+    pub struct StringNameApi in "StringName" {
+        fn intern(data: *const u8, len: usize) -> StringName {
+            if data.is_null() {
+                return StringName::NONE;
+            }
+            let bytes = unsafe { std::slice::from_raw_parts(data, len) };
+            match std::str::from_utf8(bytes) {
+                Ok(data) => StringName::new(data),
+                Err(_) => StringName::NONE,
+            }
+        }
+
+        fn intern_wide(data: *const u16, len: usize) -> StringName {
+            if data.is_null() {
+                return StringName::NONE;
+            }
+            let data = unsafe { U16Str::from_ptr(data, len) };
+            StringName::from_wide(data)
+        }
+
+        /// Returns the interned UTF-8 bytes, which stay valid for the rest of the process
+        ///
+        /// The length is written to `out_len` unless it's null.
+        fn get_utf8(name: StringName, out_len: *mut usize) -> *const u8 {
+            let data = name.as_str();
+            if !out_len.is_null() {
+                unsafe { *out_len = data.len() };
+            }
+            data.as_ptr()
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::{path::{Path, PathBuf}, time::{Duration, Instant, SystemTime}};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Polls a file's modification time and reports when it changed and then stopped changing
+///
+/// Builds write their outputs in several steps, so a change is only reported once the
+/// timestamp has been stable for `settle_time`.
+pub struct FileWatcher {
+    path: PathBuf,
+    settle_time: Duration,
+    last_poll: Instant,
+    reported_modified: Option<SystemTime>,
+    pending: Option<(SystemTime, Instant)>,
+}
+
+impl FileWatcher {
+    pub fn new<P: AsRef<Path>>(path: P, settle_time: Duration) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let reported_modified = Self::modified(&path);
+        Self {
+            path,
+            settle_time,
+            last_poll: Instant::now(),
+            reported_modified,
+            pending: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns true once per settled change. Cheap to call every frame.
+    pub fn poll(&mut self) -> bool {
+        let now = Instant::now();
+        if now - self.last_poll < POLL_INTERVAL {
+            return false;
+        }
+        self.last_poll = now;
+
+        let Some(modified) = Self::modified(&self.path) else {
+            // mid-rebuild the file can briefly disappear
+            self.pending = None;
+            return false;
+        };
+
+        if Some(modified) == self.reported_modified {
+            self.pending = None;
+            return false;
+        }
+
+        match self.pending {
+            Some((pending_modified, since)) if pending_modified == modified => {
+                if now - since >= self.settle_time {
+                    self.reported_modified = Some(modified);
+                    self.pending = None;
+                    return true;
+                }
+            },
+            _ => {
+                self.pending = Some((modified, now));
+            }
+        }
+
+        false
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|meta| meta.modified()).ok()
+    }
+}
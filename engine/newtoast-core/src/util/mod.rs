@@ -1,10 +1,15 @@
 use std::{ffi::{CStr, CString}, mem::transmute};
 
+#[cfg(feature = "dotnet")]
 use widestring::U16CString;
 
+#[cfg(feature = "dotnet")]
+pub mod file_watcher;
+pub mod string_name;
 pub mod timing;
 
 
+#[cfg(feature = "dotnet")]
 pub fn parse_hstr_wide(s: *const u16) -> Option<U16CString> {
     unsafe {
         if s.is_null() {
@@ -0,0 +1,212 @@
+use std::{collections::HashMap, fmt, sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard}};
+
+use widestring::{U16Str, U16String};
+
+
+const STORAGE_MIN_BIN_SIZE_LOG2: usize = 7; // 128
+
+// NOTE for now removal is not supported
+// Bins are allocated once and never resized, so a stored string never moves until the process
+// exits. Growing only pushes another bin, the bins themselves stay where they are.
+struct StringNameStorage {
+    data: Vec<Box<[String]>>,
+    capacity: usize,
+    len: usize,
+}
+
+impl StringNameStorage {
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            capacity: 0,
+            len: 0,
+        }
+    }
+
+    /// Stores `data` and returns its index
+    pub fn insert(&mut self, data: String) -> usize {
+        if self.len == self.capacity {
+            self.grow();
+        }
+
+        let index = self.len;
+        let (bin, offset) = Self::locate(index);
+        self.data[bin][offset] = data;
+        self.len += 1;
+        index
+    }
+
+    pub fn get(&self, index: usize) -> Option<&str> {
+        if index >= self.len {
+            return None;
+        }
+        let (bin, offset) = Self::locate(index);
+        Some(self.data[bin][offset].as_str())
+    }
+
+    // bin n holds 128 << n entries and starts where bins 0..n end
+    fn locate(index: usize) -> (usize, usize) {
+        let scaled = (index >> STORAGE_MIN_BIN_SIZE_LOG2) + 1;
+        let bin = (usize::BITS - 1 - scaled.leading_zeros()) as usize;
+        let bin_start = ((1 << bin) - 1) << STORAGE_MIN_BIN_SIZE_LOG2;
+        (bin, index - bin_start)
+    }
+
+    fn grow(&mut self) {
+        let bin_size = 1 << (self.data.len() + STORAGE_MIN_BIN_SIZE_LOG2);
+        self.data.push(vec![String::new(); bin_size].into_boxed_slice());
+        self.capacity += bin_size;
+    }
+}
+
+
+struct StringNameInterner {
+    storage: StringNameStorage,
+    names: HashMap<String, StringName>,
+    // C# hands over UTF-16, caching those separately saves transcoding on every lookup
+    wide_names: HashMap<U16String, StringName>,
+}
+
+impl StringNameInterner {
+    fn intern(&mut self, data: &str) -> StringName {
+        if let Some(&name) = self.names.get(data) {
+            return name;
+        }
+
+        let index = self.storage.insert(data.to_owned());
+        let name = StringName(index as u32 + 1);
+        self.names.insert(data.to_owned(), name);
+        name
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref STRING_NAMES: RwLock<StringNameInterner> = RwLock::new(StringNameInterner {
+        storage: StringNameStorage::new(),
+        names: HashMap::new(),
+        wide_names: HashMap::new(),
+    });
+}
+
+// interning is reachable from extern "C" exports, a poisoned lock must not turn into a panic there
+fn read_names() -> RwLockReadGuard<'static, StringNameInterner> {
+    STRING_NAMES.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write_names() -> RwLockWriteGuard<'static, StringNameInterner> {
+    STRING_NAMES.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+
+/// Handle to a globally interned string
+///
+/// Interning the same text twice returns the same handle, so comparing and hashing are integer
+/// operations. Interned strings live until the process exits. `StringName::NONE` (0) never names
+/// anything and is what the FFI exports return for invalid input.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StringName(u32);
+
+impl StringName {
+    pub const NONE: StringName = StringName(0);
+
+    pub fn new(data: &str) -> Self {
+        if let Some(name) = Self::lookup(data) {
+            return name;
+        }
+        write_names().intern(data)
+    }
+
+    /// Interns UTF-16 text, unpaired surrogates are replaced with U+FFFD
+    pub fn from_wide(data: &U16Str) -> Self {
+        if let Some(&name) = read_names().wide_names.get(data) {
+            return name;
+        }
+
+        let mut interner = write_names();
+        let name = interner.intern(&data.to_string_lossy());
+        interner.wide_names.insert(data.to_owned(), name);
+        name
+    }
+
+    /// Finds an already interned name without interning it
+    pub fn lookup(data: &str) -> Option<Self> {
+        read_names().names.get(data).copied()
+    }
+
+    pub fn from_raw(id: u32) -> Self {
+        Self(id)
+    }
+
+    pub fn id(self) -> u32 {
+        self.0
+    }
+
+    pub fn is_none(self) -> bool {
+        self == Self::NONE
+    }
+
+    /// The interned text, empty for `NONE` or an id that was never handed out
+    pub fn as_str(self) -> &'static str {
+        let Some(index) = (self.0 as usize).checked_sub(1) else {
+            return "";
+        };
+
+        let interner = read_names();
+        let data = interner.storage.get(index).unwrap_or("");
+        // SAFETY: storage bins are never reallocated and entries are never removed or
+        // overwritten, so the string's heap buffer outlives the lock guard
+        unsafe { &*(data as *const str) }
+    }
+}
+
+impl From<&str> for StringName {
+    fn from(data: &str) -> Self {
+        Self::new(data)
+    }
+}
+
+impl fmt::Display for StringName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate_bin_edges() {
+        assert_eq!(StringNameStorage::locate(0), (0, 0));
+        assert_eq!(StringNameStorage::locate(127), (0, 127));
+        assert_eq!(StringNameStorage::locate(128), (1, 0));
+        assert_eq!(StringNameStorage::locate(383), (1, 255));
+        assert_eq!(StringNameStorage::locate(384), (2, 0));
+    }
+
+    #[test]
+    fn storage_grows_past_eight_bins() {
+        let mut storage = StringNameStorage::new();
+        // 8 bins hold 128 * (2^8 - 1) entries
+        let count = 128 * 255 + 1;
+        for i in 0..count {
+            assert_eq!(storage.insert(i.to_string()), i);
+        }
+        assert_eq!(storage.get(count - 1), Some((count - 1).to_string().as_str()));
+        assert_eq!(storage.get(count), None);
+    }
+
+    #[test]
+    fn utf8_and_utf16_intern_to_the_same_name() {
+        let text = "string_name_tests_ünïcödé";
+        let wide = U16String::from_str(text);
+
+        let from_wide = StringName::from_wide(&wide);
+        assert_eq!(StringName::new(text), from_wide);
+        assert_eq!(StringName::from_wide(&wide), from_wide);
+        assert_eq!(from_wide.as_str(), text);
+        assert!(!from_wide.is_none());
+    }
+}
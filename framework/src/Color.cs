@@ -0,0 +1,25 @@
+using System;
+
+namespace NTF;
+
+// RGBA with every channel from 0 to 1, the fields are generated from the engine's Color
+public partial struct Color : IEquatable<Color>
+{
+    public static readonly Color White = new(1, 1, 1, 1);
+    public static readonly Color Black = new(0, 0, 0, 1);
+    public static readonly Color Transparent = new(0, 0, 0, 0);
+
+    public Color(float r, float g, float b, float a = 1)
+    {
+        R = r;
+        G = g;
+        B = b;
+        A = a;
+    }
+
+    public bool Equals(Color other) => R == other.R && G == other.G && B == other.B && A == other.A;
+    public override bool Equals(object? obj) => obj is Color other && Equals(other);
+    public override int GetHashCode() => HashCode.Combine(R, G, B, A);
+    public static bool operator ==(Color a, Color b) => a.Equals(b);
+    public static bool operator !=(Color a, Color b) => !a.Equals(b);
+}
@@ -1,22 +1,16 @@
 
 
 using System;
+using System.Collections.Generic;
 using System.ComponentModel.DataAnnotations;
+using System.Linq;
+using System.Reflection;
 using System.Reflection.Metadata;
 using System.Runtime.CompilerServices;
 using System.Runtime.InteropServices;
+using System.Runtime.Loader;
 
 namespace NTF;
-public delegate InitConfig GetConfigFn();
-public delegate void InitializeFn();
-public delegate void ShutdownFn();
-public delegate void UpdateFn(double dt);
-public delegate void DrawFn();
-
-public delegate void BindRenderServerFn(nint renderServer);
-public delegate void UnbindRenderServerFn();
-public delegate void FreeHstrFn(nint hstr);
-
 
 [StructLayout(LayoutKind.Sequential)]
 public struct NTEntrypointSpecifier
@@ -52,55 +46,198 @@ public class NTEntrypointSpecifierAttribute : Attribute
     }
 }
 
-public static class NTEntrypointInternal
+// Everything the engine calls is [UnmanagedCallersOnly] and bound directly by name, see
+// RuntimeEntrypoints in the engine. Signatures must stay blittable: no bool, string or
+// by-value structs containing them, pass pointers instead. An exception escaping one of these
+// takes the process down, so lifecycle methods catch everything and report it through the
+// engine's NativeErrorBuffer instead.
+public static unsafe class NTEntrypointInternal
 {
-    static NTEntrypoint? entrypointInstance;
+    // Indexed by plugin id. Slots of unloaded plugins stay null so ids are never reused
+    static readonly List<Plugin?> plugins = new();
 
-    static InitConfig SetupAndGetConfig()
+    static Plugin GetPlugin(int pluginId)
     {
-        entrypointInstance = ConstructEntrypointInstance();
-        if (entrypointInstance == null)
+        if (pluginId < 0 || pluginId >= plugins.Count || plugins[pluginId] == null)
         {
-            throw new InvalidOperationException("Entrypoint instance is not constructed");
+            throw new ArgumentOutOfRangeException(nameof(pluginId), $"No plugin with id {pluginId} is loaded");
         }
-        return entrypointInstance.GetConfig();
+        return plugins[pluginId]!;
     }
 
-    static void Initialize()
+    // A null path binds the entrypoint declared in the default context (the framework or an
+    // embedded game), anything else is loaded into its own collectible context. A non-null
+    // entryClass takes precedence over NTEntrypointSpecifier.
+    [UnmanagedCallersOnly]
+    static int LoadPlugin(nint assemblyPath, nint entryClass, int* outPluginId, NativeErrorBuffer* error)
     {
+        GameLoadContext? context = null;
+        try
+        {
+            var path = Marshal.PtrToStringUni(assemblyPath);
+            var entryClassName = Marshal.PtrToStringUni(entryClass);
+            IEnumerable<Assembly> assemblies;
+            if (path != null)
+            {
+                context = new GameLoadContext(path);
+                assemblies = new[] { context.LoadGameAssembly() };
+            }
+            else
+            {
+                assemblies = AssemblyLoadContext.Default.Assemblies;
+            }
+
+            var instance = ConstructEntrypointInstance(assemblies, entryClassName)
+                ?? throw new InvalidOperationException($"No entrypoint {entryClassName ?? "NTEntrypointSpecifier"} found in {path ?? "the default context"}");
 
-        if (entrypointInstance == null)
+            plugins.Add(new Plugin(instance, context));
+            *outPluginId = plugins.Count - 1;
+            return NativeErrorBuffer.StatusOk;
+        }
+        catch (Exception e)
         {
-            throw new Exception("Failed to construct entrypoint instance");
+            context?.Unload();
+            return error->Report(e);
         }
-        entrypointInstance.Initialize();
     }
 
-    static void Shutdown()
+    [UnmanagedCallersOnly]
+    static int UnloadPlugin(int pluginId, NativeErrorBuffer* error)
     {
-        entrypointInstance?.Shutdown();
+        try
+        {
+            var contextRef = BeginUnload(pluginId);
+            for (int i = 0; contextRef != null && contextRef.IsAlive && i < 10; i++)
+            {
+                GC.Collect();
+                GC.WaitForPendingFinalizers();
+            }
+
+            if (contextRef != null && contextRef.IsAlive)
+            {
+                Console.WriteLine($"C#: Plugin {pluginId} is still referenced after unloading, it will leak until those references are gone");
+            }
+            return NativeErrorBuffer.StatusOk;
+        }
+        catch (Exception e)
+        {
+            return error->Report(e);
+        }
     }
 
-    static void Update(double dt)
+    [UnmanagedCallersOnly]
+    static int GetPluginConfig(int pluginId, InitConfig* outConfig, NativeErrorBuffer* error)
     {
-        entrypointInstance?.Update(dt);
+        try
+        {
+            *outConfig = GetPlugin(pluginId).Entrypoint.GetConfig();
+            return NativeErrorBuffer.StatusOk;
+        }
+        catch (Exception e)
+        {
+            return error->Report(e);
+        }
     }
 
-    static void Draw()
+    [UnmanagedCallersOnly]
+    static int Initialize(int pluginId, NativeErrorBuffer* error)
     {
-        entrypointInstance?.Draw();
+        try
+        {
+            GetPlugin(pluginId).Entrypoint.Initialize();
+            return NativeErrorBuffer.StatusOk;
+        }
+        catch (Exception e)
+        {
+            return error->Report(e);
+        }
     }
 
-    static void BindRenderServer(nint renderServer)
+    [UnmanagedCallersOnly]
+    static int Shutdown(int pluginId, NativeErrorBuffer* error)
     {
-        // TODO
+        try
+        {
+            GetPlugin(pluginId).Entrypoint.Shutdown();
+            return NativeErrorBuffer.StatusOk;
+        }
+        catch (Exception e)
+        {
+            return error->Report(e);
+        }
     }
 
-    static void UnbindRenderServer()
+    [UnmanagedCallersOnly]
+    static int Update(int pluginId, double dt, NativeErrorBuffer* error)
     {
-        // TODO
+        try
+        {
+            GetPlugin(pluginId).Entrypoint.Update(dt);
+            return NativeErrorBuffer.StatusOk;
+        }
+        catch (Exception e)
+        {
+            return error->Report(e);
+        }
     }
 
+    [UnmanagedCallersOnly]
+    static int Draw(int pluginId, NativeErrorBuffer* error)
+    {
+        try
+        {
+            GetPlugin(pluginId).Entrypoint.Draw();
+            return NativeErrorBuffer.StatusOk;
+        }
+        catch (Exception e)
+        {
+            return error->Report(e);
+        }
+    }
+
+    [UnmanagedCallersOnly]
+    static int BindNativeApi(NativeApi* api, NativeErrorBuffer* error)
+    {
+        try
+        {
+            NativeApi.Bind(api);
+            return NativeErrorBuffer.StatusOk;
+        }
+        catch (Exception e)
+        {
+            return error->Report(e);
+        }
+    }
+
+    [UnmanagedCallersOnly]
+    static int BindRenderServer(nint renderServer, NativeErrorBuffer* error)
+    {
+        try
+        {
+            RenderServer.BoundRenderServer = renderServer;
+            return NativeErrorBuffer.StatusOk;
+        }
+        catch (Exception e)
+        {
+            return error->Report(e);
+        }
+    }
+
+    [UnmanagedCallersOnly]
+    static int UnbindRenderServer(NativeErrorBuffer* error)
+    {
+        try
+        {
+            RenderServer.BoundRenderServer = nint.Zero;
+            return NativeErrorBuffer.StatusOk;
+        }
+        catch (Exception e)
+        {
+            return error->Report(e);
+        }
+    }
+
+    [UnmanagedCallersOnly]
     public static void FreeHstr(nint hstr)
     {
         if (hstr != nint.Zero)
@@ -110,37 +247,64 @@ public static class NTEntrypointInternal
         }
     }
 
-    static (string, string)? IdentifyEntrypoint()
+    // Kept out of line so no stack slot in UnloadPlugin keeps the context alive
+    [MethodImpl(MethodImplOptions.NoInlining)]
+    static WeakReference? BeginUnload(int pluginId)
+    {
+        var plugin = GetPlugin(pluginId);
+        plugins[pluginId] = null;
+        if (plugin.Context == null)
+        {
+            return null;
+        }
+
+        var contextRef = new WeakReference(plugin.Context);
+        plugin.Context.Unload();
+        return contextRef;
+    }
+
+    static (Assembly, string)? IdentifyEntrypoint(IEnumerable<Assembly> assemblies)
     {
-        var assemblies = AppDomain.CurrentDomain.GetAssemblies();
         foreach (var asm in assemblies)
         {
             var allAttrs = asm.GetCustomAttributes(typeof(NTEntrypointSpecifierAttribute), false);
             var attrs = (NTEntrypointSpecifierAttribute[])allAttrs;
             if (attrs.Length > 0)
             {
-                // Console.WriteLine($"Writing Types in assembly \"{asm.FullName}\":");
-                // foreach (var type in asm.GetTypes())
-                // {
-                //     Console.WriteLine($"  {type.FullName}");
-                // }
-                return (attrs[0].AssemblyName, attrs[0].ClassName);
+                return (asm, attrs[0].ClassName);
             }
         }
 
-
         return null;
     }
 
-    static NTEntrypoint? ConstructEntrypointInstance() {
-        var maybeEntrypoint = IdentifyEntrypoint();
+    static NTEntrypoint? ConstructEntrypointInstance(IEnumerable<Assembly> assemblies, string? entryClassName) {
+        var maybeEntrypoint = entryClassName != null
+            ? assemblies.Where(asm => asm.GetType(entryClassName) != null)
+                .Select(asm => ((Assembly, string)?)(asm, entryClassName))
+                .FirstOrDefault()
+            : IdentifyEntrypoint(assemblies);
         if (maybeEntrypoint.HasValue)
         {
-            var (assemblyName, className) = maybeEntrypoint.Value;
-            return Activator.CreateInstance(assemblyName, className)?.Unwrap() as NTEntrypoint;
+            var (assembly, className) = maybeEntrypoint.Value;
+            // resolve through the assembly itself, Activator's by-name lookup can't see collectible contexts
+            var type = assembly.GetType(className);
+            return type != null ? Activator.CreateInstance(type) as NTEntrypoint : null;
         }
         return null;
     }
+
+    sealed class Plugin
+    {
+        public readonly NTEntrypoint Entrypoint;
+        public readonly GameLoadContext? Context;
+
+        public Plugin(NTEntrypoint entrypoint, GameLoadContext? context)
+        {
+            Entrypoint = entrypoint;
+            Context = context;
+        }
+    }
 }
 
 public abstract class NTEntrypoint
@@ -0,0 +1,56 @@
+using System;
+using System.IO;
+using System.Reflection;
+using System.Runtime.Loader;
+
+namespace NTF;
+
+// Collectible context for the game assembly so it can be swapped out while the engine keeps running
+internal class GameLoadContext : AssemblyLoadContext
+{
+    private readonly string assemblyDirectory;
+
+    public string AssemblyPath { get; }
+    public Assembly? GameAssembly { get; private set; }
+
+    public GameLoadContext(string assemblyPath)
+        : base($"NTGame:{Path.GetFileNameWithoutExtension(assemblyPath)}", isCollectible: true)
+    {
+        AssemblyPath = Path.GetFullPath(assemblyPath);
+        assemblyDirectory = Path.GetDirectoryName(AssemblyPath) ?? ".";
+    }
+
+    public Assembly LoadGameAssembly()
+    {
+        GameAssembly = LoadFromBytes(AssemblyPath);
+        return GameAssembly;
+    }
+
+    protected override Assembly? Load(AssemblyName assemblyName)
+    {
+        // The framework has to come from the default context, otherwise the game's
+        // NTEntrypoint would be a different type than the one the engine talks to
+        if (assemblyName.Name == typeof(NTEntrypoint).Assembly.GetName().Name)
+        {
+            return null;
+        }
+
+        var candidate = Path.Combine(assemblyDirectory, $"{assemblyName.Name}.dll");
+        return File.Exists(candidate) ? LoadFromBytes(candidate) : null;
+    }
+
+    // Loading from memory keeps the file unlocked so the next build can overwrite it
+    private Assembly LoadFromBytes(string path)
+    {
+        using var assemblyStream = new MemoryStream(File.ReadAllBytes(path));
+
+        var symbolsPath = Path.ChangeExtension(path, ".pdb");
+        if (File.Exists(symbolsPath))
+        {
+            using var symbolsStream = new MemoryStream(File.ReadAllBytes(symbolsPath));
+            return LoadFromStream(assemblyStream, symbolsStream);
+        }
+
+        return LoadFromStream(assemblyStream);
+    }
+}
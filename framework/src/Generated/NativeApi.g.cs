@@ -0,0 +1,333 @@
+// <auto-generated>
+// Generated from the engine's native_exports! tables, regenerate with `just generate-interop`.
+// </auto-generated>
+
+using System;
+using System.Runtime.InteropServices;
+using GlmSharp;
+
+namespace NTF;
+
+[StructLayout(LayoutKind.Sequential)]
+public partial struct Transform2D
+{
+    public vec2 XAxis;
+    public vec2 YAxis;
+    public vec2 Translation;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+public partial struct Color
+{
+    public float R;
+    public float G;
+    public float B;
+    public float A;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+public partial struct ShaderParameterInfo
+{
+    public uint Name;
+    public ShaderValueType ValueType;
+    public int ArrayLen;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal unsafe partial struct NativeApi
+{
+    public const uint Version = 10;
+
+    public uint ApiVersion;
+    public uint Size;
+    public StringNameApi StringName;
+    public RenderServerApi RenderServer;
+    public TestApi Test;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal unsafe struct StringNameApi
+{
+    public delegate* unmanaged<byte*, nuint, uint> Intern;
+    public delegate* unmanaged<ushort*, nuint, uint> InternWide;
+    public delegate* unmanaged<uint, nuint*, byte*> GetUtf8;
+}
+
+internal static unsafe class StringNameInternal
+{
+    static ref readonly StringNameApi Api => ref NativeApi.Current.StringName;
+
+    internal static uint Intern(byte* data, nuint len)
+        => Api.Intern(data, len);
+
+    internal static uint InternWide(ushort* data, nuint len)
+        => Api.InternWide(data, len);
+
+    // Returns the interned UTF-8 bytes, which stay valid for the rest of the process
+    //
+    // The length is written to `out_len` unless it's null.
+    internal static byte* GetUtf8(uint name, nuint* outLen)
+        => Api.GetUtf8(name, outLen);
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal unsafe struct RenderServerApi
+{
+    public delegate* unmanaged<nint, uint> Renderable2DCreate;
+    public delegate* unmanaged<nint, uint, void> Renderable2DDestroy;
+    public delegate* unmanaged<nint, uint, Transform2D, void> Renderable2DSetTransform;
+    public delegate* unmanaged<nint, uint, Transform2D> Renderable2DGetTransform;
+    public delegate* unmanaged<nint, uint, nint, byte> Renderable2DSetUserData;
+    public delegate* unmanaged<nint, uint, nint> Renderable2DGetUserData;
+    public delegate* unmanaged<nint, uint> Material2DCreate;
+    public delegate* unmanaged<nint, uint, void> Material2DDestroy;
+    public delegate* unmanaged<nint, uint, uint, byte*, nuint, nuint*, byte> Material2DSetShader;
+    public delegate* unmanaged<nint, byte*, nuint, byte*, nuint, byte*, nuint, nuint*, uint> Shader2DCreate;
+    public delegate* unmanaged<nint, uint, void> Shader2DDestroy;
+    public delegate* unmanaged<nint, uint, nuint> Shader2DGetUniformCount;
+    public delegate* unmanaged<nint, uint, nuint, ShaderParameterInfo*, byte> Shader2DGetUniform;
+    public delegate* unmanaged<nint, uint, nuint> Shader2DGetAttributeCount;
+    public delegate* unmanaged<nint, uint, nuint, ShaderParameterInfo*, byte> Shader2DGetAttribute;
+    public delegate* unmanaged<nint, uint, uint, float, byte*, nuint, nuint*, byte> Material2DSetFloat;
+    public delegate* unmanaged<nint, uint, uint, int, byte*, nuint, nuint*, byte> Material2DSetInt;
+    public delegate* unmanaged<nint, uint, uint, vec2, byte*, nuint, nuint*, byte> Material2DSetVec2;
+    public delegate* unmanaged<nint, uint, uint, vec3, byte*, nuint, nuint*, byte> Material2DSetVec3;
+    public delegate* unmanaged<nint, uint, uint, float*, byte*, nuint, nuint*, byte> Material2DSetVec4;
+    public delegate* unmanaged<nint, uint, uint, float*, byte*, nuint, nuint*, byte> Material2DSetMat3;
+    public delegate* unmanaged<nint, uint, uint, float*, byte*, nuint, nuint*, byte> Material2DSetMat4;
+    public delegate* unmanaged<nint, uint, uint, Color, byte*, nuint, nuint*, byte> Material2DSetColor;
+    public delegate* unmanaged<nint, uint, uint, int, byte*, nuint, nuint*, byte> Material2DSetTextureSlot;
+    public delegate* unmanaged<nint, uint, uint, uint, byte> Renderable2DAddSurface;
+    public delegate* unmanaged<nint, uint, void> Renderable2DClearSurfaces;
+    public delegate* unmanaged<nint, vec2*, vec2*, Color*, nuint, uint*, nuint, byte, byte*, nuint, nuint*, uint> Mesh2DCreate;
+    public delegate* unmanaged<nint, uint, vec2*, vec2*, Color*, nuint, uint*, nuint, byte*, nuint, nuint*, byte> Mesh2DUpdate;
+    public delegate* unmanaged<nint, uint, void> Mesh2DDestroy;
+}
+
+internal static unsafe class RenderServerInternal
+{
+    static ref readonly RenderServerApi Api => ref NativeApi.Current.RenderServer;
+
+    internal static uint Renderable2DCreate(nint renderServer)
+        => Api.Renderable2DCreate(renderServer);
+
+    internal static void Renderable2DDestroy(nint renderServer, uint id)
+        => Api.Renderable2DDestroy(renderServer, id);
+
+    internal static void Renderable2DSetTransform(nint renderServer, uint id, Transform2D transform)
+        => Api.Renderable2DSetTransform(renderServer, id, transform);
+
+    internal static Transform2D Renderable2DGetTransform(nint renderServer, uint id)
+        => Api.Renderable2DGetTransform(renderServer, id);
+
+    // Takes ownership of `user_data`, a GCHandle or null
+    //
+    // Returns 0 if the renderable doesn't exist, the caller then still owns `user_data`.
+    internal static byte Renderable2DSetUserData(nint renderServer, uint id, nint userData)
+        => Api.Renderable2DSetUserData(renderServer, id, userData);
+
+    // The handle stays owned by the renderable, don't free it
+    internal static nint Renderable2DGetUserData(nint renderServer, uint id)
+        => Api.Renderable2DGetUserData(renderServer, id);
+
+    internal static uint Material2DCreate(nint renderServer)
+        => Api.Material2DCreate(renderServer);
+
+    internal static void Material2DDestroy(nint renderServer, uint id)
+        => Api.Material2DDestroy(renderServer, id);
+
+    // Returns 0 if the material or shader doesn't exist, with the reason written to
+    // `error_log` like `shader_2d_create`
+    //
+    // Parameters the new shader doesn't declare with the same type are dropped.
+    internal static byte Material2DSetShader(nint renderServer, uint id, uint shader, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Material2DSetShader(renderServer, id, shader, errorLog, errorLogCapacity, errorLogLen);
+
+    // The sources are UTF-8 and don't need a terminator
+    //
+    // Returns 0 on failure, with the compiler or linker log written to `error_log` as UTF-8,
+    // truncated to `error_log_capacity` bytes.
+    internal static uint Shader2DCreate(nint renderServer, byte* vertexSrc, nuint vertexLen, byte* fragmentSrc, nuint fragmentLen, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Shader2DCreate(renderServer, vertexSrc, vertexLen, fragmentSrc, fragmentLen, errorLog, errorLogCapacity, errorLogLen);
+
+    internal static void Shader2DDestroy(nint renderServer, uint id)
+        => Api.Shader2DDestroy(renderServer, id);
+
+    // 0 for a shader that doesn't exist
+    internal static nuint Shader2DGetUniformCount(nint renderServer, uint id)
+        => Api.Shader2DGetUniformCount(renderServer, id);
+
+    // Returns 0 and leaves `out_info` alone if the shader or index doesn't exist
+    internal static byte Shader2DGetUniform(nint renderServer, uint id, nuint index, ShaderParameterInfo* outInfo)
+        => Api.Shader2DGetUniform(renderServer, id, index, outInfo);
+
+    // 0 for a shader that doesn't exist
+    internal static nuint Shader2DGetAttributeCount(nint renderServer, uint id)
+        => Api.Shader2DGetAttributeCount(renderServer, id);
+
+    // Returns 0 and leaves `out_info` alone if the shader or index doesn't exist
+    internal static byte Shader2DGetAttribute(nint renderServer, uint id, nuint index, ShaderParameterInfo* outInfo)
+        => Api.Shader2DGetAttribute(renderServer, id, index, outInfo);
+
+    // The `material_2d_set_*` setters return 0 if the material's shader has no active uniform
+    // `name` of that type, if it's an array or if a `value` pointer is null, with the reason
+    // written to `error_log` like `shader_2d_create`
+    internal static byte Material2DSetFloat(nint renderServer, uint id, uint name, float value, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Material2DSetFloat(renderServer, id, name, value, errorLog, errorLogCapacity, errorLogLen);
+
+    internal static byte Material2DSetInt(nint renderServer, uint id, uint name, int value, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Material2DSetInt(renderServer, id, name, value, errorLog, errorLogCapacity, errorLogLen);
+
+    internal static byte Material2DSetVec2(nint renderServer, uint id, uint name, vec2 value, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Material2DSetVec2(renderServer, id, name, value, errorLog, errorLogCapacity, errorLogLen);
+
+    internal static byte Material2DSetVec3(nint renderServer, uint id, uint name, vec3 value, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Material2DSetVec3(renderServer, id, name, value, errorLog, errorLogCapacity, errorLogLen);
+
+    // `value` points to 4 floats, `Vec4` is SIMD-aligned so it isn't passed by value
+    internal static byte Material2DSetVec4(nint renderServer, uint id, uint name, float* value, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Material2DSetVec4(renderServer, id, name, value, errorLog, errorLogCapacity, errorLogLen);
+
+    // `value` points to 9 floats, column-major
+    internal static byte Material2DSetMat3(nint renderServer, uint id, uint name, float* value, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Material2DSetMat3(renderServer, id, name, value, errorLog, errorLogCapacity, errorLogLen);
+
+    // `value` points to 16 floats, column-major
+    internal static byte Material2DSetMat4(nint renderServer, uint id, uint name, float* value, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Material2DSetMat4(renderServer, id, name, value, errorLog, errorLogCapacity, errorLogLen);
+
+    // Sets a `vec4` uniform
+    internal static byte Material2DSetColor(nint renderServer, uint id, uint name, Color value, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Material2DSetColor(renderServer, id, name, value, errorLog, errorLogCapacity, errorLogLen);
+
+    // Points a `sampler2D` uniform at texture unit `slot`
+    internal static byte Material2DSetTextureSlot(nint renderServer, uint id, uint name, int slot, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Material2DSetTextureSlot(renderServer, id, name, slot, errorLog, errorLogCapacity, errorLogLen);
+
+    // Surfaces draw in the order they're added, returns 0 if the renderable doesn't exist
+    //
+    // The material's shader gets the renderable's transform in `u_transform` if it declares it.
+    internal static byte Renderable2DAddSurface(nint renderServer, uint renderable, uint material, uint mesh)
+        => Api.Renderable2DAddSurface(renderServer, renderable, material, mesh);
+
+    internal static void Renderable2DClearSurfaces(nint renderServer, uint renderable)
+        => Api.Renderable2DClearSurfaces(renderServer, renderable);
+
+    // `uvs`, `colors` and `indices` may be null, every non-null array is read before returning
+    //
+    // Returns 0 if the mesh couldn't be created, with the reason written to `error_log` like
+    // `shader_2d_create`.
+    internal static uint Mesh2DCreate(nint renderServer, vec2* positions, vec2* uvs, Color* colors, nuint vertexCount, uint* indices, nuint indexCount, byte dynamic, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Mesh2DCreate(renderServer, positions, uvs, colors, vertexCount, indices, indexCount, dynamic, errorLog, errorLogCapacity, errorLogLen);
+
+    // Same arrays and error log as `mesh_2d_create`, returns 0 if the update failed
+    internal static byte Mesh2DUpdate(nint renderServer, uint id, vec2* positions, vec2* uvs, Color* colors, nuint vertexCount, uint* indices, nuint indexCount, byte* errorLog, nuint errorLogCapacity, nuint* errorLogLen)
+        => Api.Mesh2DUpdate(renderServer, id, positions, uvs, colors, vertexCount, indices, indexCount, errorLog, errorLogCapacity, errorLogLen);
+
+    internal static void Mesh2DDestroy(nint renderServer, uint id)
+        => Api.Mesh2DDestroy(renderServer, id);
+}
+
+public static partial class RenderServer
+{
+    public static uint Renderable2DCreate()
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        return RenderServerInternal.Renderable2DCreate(BoundRenderServer);
+    }
+
+    public static void Renderable2DDestroy(uint id)
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        RenderServerInternal.Renderable2DDestroy(BoundRenderServer, id);
+    }
+
+    public static void Renderable2DSetTransform(uint id, Transform2D transform)
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        RenderServerInternal.Renderable2DSetTransform(BoundRenderServer, id, transform);
+    }
+
+    public static Transform2D Renderable2DGetTransform(uint id)
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        return RenderServerInternal.Renderable2DGetTransform(BoundRenderServer, id);
+    }
+
+    public static uint Material2DCreate()
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        return RenderServerInternal.Material2DCreate(BoundRenderServer);
+    }
+
+    public static void Material2DDestroy(uint id)
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        RenderServerInternal.Material2DDestroy(BoundRenderServer, id);
+    }
+
+    public static void Shader2DDestroy(uint id)
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        RenderServerInternal.Shader2DDestroy(BoundRenderServer, id);
+    }
+
+    // Surfaces draw in the order they're added, returns 0 if the renderable doesn't exist
+    //
+    // The material's shader gets the renderable's transform in `u_transform` if it declares it.
+    public static byte Renderable2DAddSurface(uint renderable, uint material, uint mesh)
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        return RenderServerInternal.Renderable2DAddSurface(BoundRenderServer, renderable, material, mesh);
+    }
+
+    public static void Renderable2DClearSurfaces(uint renderable)
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        RenderServerInternal.Renderable2DClearSurfaces(BoundRenderServer, renderable);
+    }
+
+    public static void Mesh2DDestroy(uint id)
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        RenderServerInternal.Mesh2DDestroy(BoundRenderServer, id);
+    }
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal unsafe struct TestApi
+{
+    public delegate* unmanaged<int*> InternalPtrReturnCall;
+    public delegate* unmanaged<byte*, void> ModifyRef;
+}
+
+internal static unsafe class TestInternal
+{
+    static ref readonly TestApi Api => ref NativeApi.Current.Test;
+
+    internal static int* InternalPtrReturnCall()
+        => Api.InternalPtrReturnCall();
+
+    internal static void ModifyRef(byte* u8Ref)
+        => Api.ModifyRef(u8Ref);
+}
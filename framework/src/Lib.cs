@@ -1,20 +1,13 @@
 using System;
-using System.Runtime.InteropServices;
 
 
 namespace NTF;
 
-public static partial class Lib
+public static unsafe class Lib
 {
+    public static IntPtr TestInternalPtrReturnCall()
+        => (IntPtr)TestInternal.InternalPtrReturnCall();
 
-    const string LIB_NEWTOAST_CORE = "newtoast_core";
-
-    // [LibraryImport(LIB_NEWTOAST_CORE, EntryPoint = "test_internal_call")]
-    // public static partial int TestInternalCall(int x);
-
-    [LibraryImport(LIB_NEWTOAST_CORE, EntryPoint = "test_internal_ptr_return_call")]
-    public static partial IntPtr TestInternalPtrReturnCall();
-
-    [LibraryImport(LIB_NEWTOAST_CORE, EntryPoint = "test_modify_ref")]
-    public static partial void TestModifyRef(nint foo);
-}
\ No newline at end of file
+    public static void TestModifyRef(nint foo)
+        => TestInternal.ModifyRef((byte*)foo);
+}
@@ -0,0 +1,65 @@
+using System;
+using System.Runtime.InteropServices;
+
+namespace NTF;
+
+// Hands managed objects to the engine as GCHandles. Once a handle is passed to an engine call
+// that takes ownership (e.g. Renderable2D user data) the engine frees it, don't call Free on it.
+// Handles keep their target alive, including a plugin's collectible load context.
+public static class ManagedHandle
+{
+    public static nint Alloc(object target)
+    {
+        return GCHandle.ToIntPtr(GCHandle.Alloc(target));
+    }
+
+    public static object? Get(nint handle)
+    {
+        return handle != nint.Zero ? GCHandle.FromIntPtr(handle).Target : null;
+    }
+
+    public static T? Get<T>(nint handle) where T : class
+    {
+        return Get(handle) as T;
+    }
+
+    public static void Free(nint handle)
+    {
+        if (handle != nint.Zero)
+        {
+            GCHandle.FromIntPtr(handle).Free();
+        }
+    }
+}
+
+// Bound by install_managed_handles in the engine, so ManagedHandle can free and clone on drop
+internal static class ManagedHandleInternal
+{
+    [UnmanagedCallersOnly]
+    static void FreeHandle(nint handle)
+    {
+        try
+        {
+            ManagedHandle.Free(handle);
+        }
+        catch (Exception e)
+        {
+            Console.WriteLine($"C#: Failed to free handle {handle}: {e}");
+        }
+    }
+
+    [UnmanagedCallersOnly]
+    static nint CloneHandle(nint handle)
+    {
+        try
+        {
+            var target = ManagedHandle.Get(handle);
+            return target != null ? ManagedHandle.Alloc(target) : nint.Zero;
+        }
+        catch (Exception e)
+        {
+            Console.WriteLine($"C#: Failed to clone handle {handle}: {e}");
+            return nint.Zero;
+        }
+    }
+}
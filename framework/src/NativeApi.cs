@@ -0,0 +1,36 @@
+using System;
+
+namespace NTF;
+
+// The engine hands this over through BindNativeApi before any plugin loads, every native call
+// goes through it instead of a library looked up by name. The fields and tables are generated
+// from the engine's declarations, see Generated/NativeApi.g.cs.
+internal unsafe partial struct NativeApi
+{
+    static NativeApi current;
+    static bool isBound;
+
+    internal static ref readonly NativeApi Current
+    {
+        get
+        {
+            if (!isBound)
+                throw new InvalidOperationException("The native API is not bound yet.");
+            return ref current;
+        }
+    }
+
+    internal static void Bind(NativeApi* api)
+    {
+        if (api == null)
+            throw new ArgumentNullException(nameof(api));
+        if (api->ApiVersion != Version)
+            throw new InvalidOperationException($"Native API version {api->ApiVersion} does not match the framework's {Version}, rebuild NT against this engine.");
+        if (api->Size != (uint)sizeof(NativeApi))
+            throw new InvalidOperationException($"Native API is {api->Size} bytes but the framework expects {sizeof(NativeApi)}, the layouts have diverged.");
+
+        // the table is a static in the engine, but a copy keeps it reachable without a pointer
+        current = *api;
+        isBound = true;
+    }
+}
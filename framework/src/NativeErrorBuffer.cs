@@ -0,0 +1,42 @@
+using System;
+using System.Text;
+using System.Runtime.InteropServices;
+
+namespace NTF;
+
+// Mirrors RawManagedError in the engine. The engine owns both buffers, exceptions thrown by
+// lifecycle callbacks are written here as truncated UTF-8 instead of unwinding into native code.
+[StructLayout(LayoutKind.Sequential)]
+public unsafe struct NativeErrorBuffer
+{
+    public const int StatusOk = 0;
+    public const int StatusException = 1;
+
+    public byte* Message;
+    public nuint MessageCapacity;
+    public nuint MessageLength;
+    public byte* StackTrace;
+    public nuint StackTraceCapacity;
+    public nuint StackTraceLength;
+
+    public int Report(Exception e)
+    {
+        MessageLength = Write($"{e.GetType().FullName}: {e.Message}", Message, MessageCapacity);
+        // ToString keeps inner exceptions, StackTrace alone would drop them
+        StackTraceLength = Write(e.ToString(), StackTrace, StackTraceCapacity);
+        return StatusException;
+    }
+
+    static nuint Write(string text, byte* buffer, nuint capacity)
+    {
+        if (buffer == null || capacity == 0)
+        {
+            return 0;
+        }
+
+        var bytes = Encoding.UTF8.GetBytes(text);
+        var length = Math.Min((nuint)bytes.Length, capacity);
+        bytes.AsSpan(0, (int)length).CopyTo(new Span<byte>(buffer, (int)length));
+        return length;
+    }
+}
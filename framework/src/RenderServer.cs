@@ -1,96 +1,260 @@
 using System;
+using System.Text;
+using GlmSharp;
 
 namespace NTF;
-public static class RenderServer
+
+// The bound-checked wrappers are generated, see Generated/NativeApi.g.cs
+public static partial class RenderServer
 {
     internal static nint BoundRenderServer;
     internal static bool IsRenderServerBound => BoundRenderServer != nint.Zero;
 
-    public static uint Renderable2DCreate()
+    const int ShaderErrorLogCapacity = 16 * 1024;
+
+    // Vertex inputs are bound by name: a_position (vec2), a_uv (vec2) and a_color (vec4)
+    public static unsafe uint Shader2DCreate(string vertexSource, string fragmentSource)
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        byte[] vertex = Encoding.UTF8.GetBytes(vertexSource);
+        byte[] fragment = Encoding.UTF8.GetBytes(fragmentSource);
+        byte[] errorLog = new byte[ShaderErrorLogCapacity];
+        nuint errorLogLength;
+        fixed (byte* vertexPtr = vertex)
+        fixed (byte* fragmentPtr = fragment)
+        fixed (byte* errorLogPtr = errorLog)
+        {
+            uint id = RenderServerInternal.Shader2DCreate(BoundRenderServer, vertexPtr, (nuint)vertex.Length,
+                fragmentPtr, (nuint)fragment.Length, errorLogPtr, (nuint)errorLog.Length, &errorLogLength);
+            if (id == 0)
+                throw new ShaderCompileException(Encoding.UTF8.GetString(errorLog, 0, (int)errorLogLength));
+            return id;
+        }
+    }
+
+    // Empty for a shader that doesn't exist
+    public static unsafe ShaderParameterInfo[] Shader2DGetUniforms(uint id)
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        var uniforms = new ShaderParameterInfo[(int)RenderServerInternal.Shader2DGetUniformCount(BoundRenderServer, id)];
+        fixed (ShaderParameterInfo* uniformsPtr = uniforms)
+        {
+            for (int i = 0; i < uniforms.Length; i++)
+                RenderServerInternal.Shader2DGetUniform(BoundRenderServer, id, (nuint)i, uniformsPtr + i);
+        }
+        return uniforms;
+    }
+
+    // Empty for a shader that doesn't exist
+    public static unsafe ShaderParameterInfo[] Shader2DGetAttributes(uint id)
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        var attributes = new ShaderParameterInfo[(int)RenderServerInternal.Shader2DGetAttributeCount(BoundRenderServer, id)];
+        fixed (ShaderParameterInfo* attributesPtr = attributes)
+        {
+            for (int i = 0; i < attributes.Length; i++)
+                RenderServerInternal.Shader2DGetAttribute(BoundRenderServer, id, (nuint)i, attributesPtr + i);
+        }
+        return attributes;
+    }
+
+    const int MeshErrorLogCapacity = 1024;
+
+    // Empty uvs upload as zero, empty colors as white, empty indices draw a plain triangle list.
+    // The spans are copied before returning.
+    public static unsafe uint Mesh2DCreate(ReadOnlySpan<vec2> positions, ReadOnlySpan<vec2> uvs = default,
+        ReadOnlySpan<Color> colors = default, ReadOnlySpan<uint> indices = default, bool dynamic = false)
     {
         if (!IsRenderServerBound)
             throw new InvalidOperationException("RenderServer is not bound.");
+        CheckMeshAttributes(positions, uvs, colors);
 
-        return RenderServerInternal.Renderable2DCreate(BoundRenderServer);
+        fixed (vec2* positionsPtr = positions)
+        fixed (vec2* uvsPtr = uvs)
+        fixed (Color* colorsPtr = colors)
+        fixed (uint* indicesPtr = indices)
+        {
+            byte* errorLog = stackalloc byte[MeshErrorLogCapacity];
+            nuint errorLogLength;
+            uint id = RenderServerInternal.Mesh2DCreate(BoundRenderServer, positionsPtr, uvsPtr, colorsPtr,
+                (nuint)positions.Length, indicesPtr, (nuint)indices.Length, dynamic ? (byte)1 : (byte)0,
+                errorLog, MeshErrorLogCapacity, &errorLogLength);
+            if (id == 0)
+                throw new InvalidOperationException(Encoding.UTF8.GetString(errorLog, checked((int)errorLogLength)));
+            return id;
+        }
     }
 
-    public static void Renderable2DDestroy(uint id)
+    // Overwrites the mesh in place if the vertex and index counts are unchanged, best for meshes
+    // created as dynamic
+    public static unsafe void Mesh2DUpdate(uint id, ReadOnlySpan<vec2> positions, ReadOnlySpan<vec2> uvs = default,
+        ReadOnlySpan<Color> colors = default, ReadOnlySpan<uint> indices = default)
     {
         if (!IsRenderServerBound)
             throw new InvalidOperationException("RenderServer is not bound.");
+        CheckMeshAttributes(positions, uvs, colors);
 
-        RenderServerInternal.Renderable2DDestroy(BoundRenderServer, id);
+        fixed (vec2* positionsPtr = positions)
+        fixed (vec2* uvsPtr = uvs)
+        fixed (Color* colorsPtr = colors)
+        fixed (uint* indicesPtr = indices)
+        {
+            byte* errorLog = stackalloc byte[MeshErrorLogCapacity];
+            nuint errorLogLength;
+            byte updated = RenderServerInternal.Mesh2DUpdate(BoundRenderServer, id, positionsPtr, uvsPtr, colorsPtr,
+                (nuint)positions.Length, indicesPtr, (nuint)indices.Length, errorLog, MeshErrorLogCapacity, &errorLogLength);
+            if (updated == 0)
+                throw new InvalidOperationException(Encoding.UTF8.GetString(errorLog, checked((int)errorLogLength)));
+        }
     }
 
-    public static void Renderable2DSetTransform(uint id, uint transform)
+    // The engine reads positions.Length elements from every attribute it's handed
+    static void CheckMeshAttributes(ReadOnlySpan<vec2> positions, ReadOnlySpan<vec2> uvs, ReadOnlySpan<Color> colors)
+    {
+        if (!uvs.IsEmpty && uvs.Length != positions.Length)
+            throw new ArgumentException($"Expected {positions.Length} UVs, got {uvs.Length}.", nameof(uvs));
+        if (!colors.IsEmpty && colors.Length != positions.Length)
+            throw new ArgumentException($"Expected {positions.Length} colors, got {colors.Length}.", nameof(colors));
+    }
+
+    const int ParamErrorLogCapacity = 1024;
+
+    // Throws an ArgumentException if the material or shader doesn't exist. Parameters the new
+    // shader doesn't declare with the same type are dropped.
+    public static unsafe void Material2DSetShader(uint id, uint shader)
     {
         if (!IsRenderServerBound)
             throw new InvalidOperationException("RenderServer is not bound.");
 
-        RenderServerInternal.Renderable2DSetTransform(BoundRenderServer, id, transform);
+        byte* errorLog = stackalloc byte[ParamErrorLogCapacity];
+        nuint errorLogLength;
+        byte result = RenderServerInternal.Material2DSetShader(BoundRenderServer, id, shader,
+            errorLog, ParamErrorLogCapacity, &errorLogLength);
+        ThrowIfParamFailed(result, errorLog, errorLogLength);
     }
 
-    public static uint Renderable2DSetTarget(uint id)
+    // The Material2DSetShaderParam overloads throw an ArgumentException if the material's shader has
+    // no active uniform of that name and type, or if it's an array. Values are uploaded each time a
+    // surface using the material is drawn.
+    public static unsafe void Material2DSetShaderParam(uint id, StringName name, float value)
     {
         if (!IsRenderServerBound)
             throw new InvalidOperationException("RenderServer is not bound.");
 
-        return RenderServerInternal.Renderable2DSetTarget(BoundRenderServer, id);
+        byte* errorLog = stackalloc byte[ParamErrorLogCapacity];
+        nuint errorLogLength;
+        byte result = RenderServerInternal.Material2DSetFloat(BoundRenderServer, id, name.Id, value,
+            errorLog, ParamErrorLogCapacity, &errorLogLength);
+        ThrowIfParamFailed(result, errorLog, errorLogLength);
     }
 
-    public static void Renderable2DSetTargetWithTarget(uint id, uint target)
+    public static unsafe void Material2DSetShaderParam(uint id, StringName name, int value)
     {
         if (!IsRenderServerBound)
             throw new InvalidOperationException("RenderServer is not bound.");
 
-        RenderServerInternal.Renderable2DSetTargetWithTarget(BoundRenderServer, id, target);
+        byte* errorLog = stackalloc byte[ParamErrorLogCapacity];
+        nuint errorLogLength;
+        byte result = RenderServerInternal.Material2DSetInt(BoundRenderServer, id, name.Id, value,
+            errorLog, ParamErrorLogCapacity, &errorLogLength);
+        ThrowIfParamFailed(result, errorLog, errorLogLength);
     }
 
-    public static uint Material2DCreate()
+    public static unsafe void Material2DSetShaderParam(uint id, StringName name, vec2 value)
     {
         if (!IsRenderServerBound)
             throw new InvalidOperationException("RenderServer is not bound.");
 
-        return RenderServerInternal.Material2DCreate(BoundRenderServer);
+        byte* errorLog = stackalloc byte[ParamErrorLogCapacity];
+        nuint errorLogLength;
+        byte result = RenderServerInternal.Material2DSetVec2(BoundRenderServer, id, name.Id, value,
+            errorLog, ParamErrorLogCapacity, &errorLogLength);
+        ThrowIfParamFailed(result, errorLog, errorLogLength);
     }
 
-    public static void Material2DDestroy(uint id)
+    public static unsafe void Material2DSetShaderParam(uint id, StringName name, vec3 value)
     {
         if (!IsRenderServerBound)
             throw new InvalidOperationException("RenderServer is not bound.");
 
-        RenderServerInternal.Material2DDestroy(BoundRenderServer, id);
+        byte* errorLog = stackalloc byte[ParamErrorLogCapacity];
+        nuint errorLogLength;
+        byte result = RenderServerInternal.Material2DSetVec3(BoundRenderServer, id, name.Id, value,
+            errorLog, ParamErrorLogCapacity, &errorLogLength);
+        ThrowIfParamFailed(result, errorLog, errorLogLength);
     }
 
-    public static void Material2DSetShader(uint id, uint shader)
+    public static unsafe void Material2DSetShaderParam(uint id, StringName name, vec4 value)
     {
         if (!IsRenderServerBound)
             throw new InvalidOperationException("RenderServer is not bound.");
 
-        RenderServerInternal.Material2DSetShader(BoundRenderServer, id, shader);
+        byte* errorLog = stackalloc byte[ParamErrorLogCapacity];
+        nuint errorLogLength;
+        byte result = RenderServerInternal.Material2DSetVec4(BoundRenderServer, id, name.Id, (float*)&value,
+            errorLog, ParamErrorLogCapacity, &errorLogLength);
+        ThrowIfParamFailed(result, errorLog, errorLogLength);
     }
 
-    public static uint Renderable2DAddSurface(uint renderable, uint material, uint mesh)
+    public static unsafe void Material2DSetShaderParam(uint id, StringName name, mat3 value)
     {
         if (!IsRenderServerBound)
             throw new InvalidOperationException("RenderServer is not bound.");
 
-        return RenderServerInternal.Renderable2DAddSurface(BoundRenderServer, renderable, material, mesh);
+        byte* errorLog = stackalloc byte[ParamErrorLogCapacity];
+        nuint errorLogLength;
+        byte result = RenderServerInternal.Material2DSetMat3(BoundRenderServer, id, name.Id, (float*)&value,
+            errorLog, ParamErrorLogCapacity, &errorLogLength);
+        ThrowIfParamFailed(result, errorLog, errorLogLength);
     }
 
-    public static void Renderable2DClearSurfaces(uint renderable)
+    public static unsafe void Material2DSetShaderParam(uint id, StringName name, mat4 value)
     {
         if (!IsRenderServerBound)
             throw new InvalidOperationException("RenderServer is not bound.");
 
-        RenderServerInternal.Renderable2DClearSurfaces(BoundRenderServer, renderable);
+        byte* errorLog = stackalloc byte[ParamErrorLogCapacity];
+        nuint errorLogLength;
+        byte result = RenderServerInternal.Material2DSetMat4(BoundRenderServer, id, name.Id, (float*)&value,
+            errorLog, ParamErrorLogCapacity, &errorLogLength);
+        ThrowIfParamFailed(result, errorLog, errorLogLength);
     }
 
-    public static void Mesh2DDestroy(uint id)
+    // Sets a vec4 uniform
+    public static unsafe void Material2DSetShaderParam(uint id, StringName name, Color value)
     {
         if (!IsRenderServerBound)
             throw new InvalidOperationException("RenderServer is not bound.");
 
-        RenderServerInternal.Mesh2DDestroy(BoundRenderServer, id);
+        byte* errorLog = stackalloc byte[ParamErrorLogCapacity];
+        nuint errorLogLength;
+        byte result = RenderServerInternal.Material2DSetColor(BoundRenderServer, id, name.Id, value,
+            errorLog, ParamErrorLogCapacity, &errorLogLength);
+        ThrowIfParamFailed(result, errorLog, errorLogLength);
+    }
+
+    // Points a sampler2D uniform at texture unit slot
+    public static unsafe void Material2DSetTextureSlot(uint id, StringName name, int slot)
+    {
+        if (!IsRenderServerBound)
+            throw new InvalidOperationException("RenderServer is not bound.");
+
+        byte* errorLog = stackalloc byte[ParamErrorLogCapacity];
+        nuint errorLogLength;
+        byte result = RenderServerInternal.Material2DSetTextureSlot(BoundRenderServer, id, name.Id, slot,
+            errorLog, ParamErrorLogCapacity, &errorLogLength);
+        ThrowIfParamFailed(result, errorLog, errorLogLength);
+    }
+
+    static unsafe void ThrowIfParamFailed(byte result, byte* errorLog, nuint errorLogLength)
+    {
+        if (result == 0)
+            throw new ArgumentException(Encoding.UTF8.GetString(errorLog, checked((int)errorLogLength)));
     }
-}
\ No newline at end of file
+}
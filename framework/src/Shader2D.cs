@@ -0,0 +1,36 @@
+using System;
+
+namespace NTF;
+
+// Matches the engine's ShaderValueType
+public enum ShaderValueType : uint
+{
+    // Anything materials can't set, e.g. bvec2 or sampler3D
+    Unsupported = 0,
+    Float,
+    Int,
+    Vec2,
+    Vec3,
+    Vec4,
+    Mat3,
+    Mat4,
+    Sampler2D,
+}
+
+// A uniform or attribute the linked shader declares, arrays are named without the [0]
+public partial struct ShaderParameterInfo
+{
+    public StringName ParameterName => StringName.FromId(Name);
+
+    public override string ToString() => ArrayLen > 1
+        ? $"{ValueType} {ParameterName}[{ArrayLen}]"
+        : $"{ValueType} {ParameterName}";
+}
+
+// Thrown by RenderServer.Shader2DCreate, the message is the compiler or linker log
+public class ShaderCompileException : Exception
+{
+    public ShaderCompileException(string log) : base(log)
+    {
+    }
+}
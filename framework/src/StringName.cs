@@ -0,0 +1,48 @@
+using System;
+using System.Runtime.InteropServices;
+using System.Text;
+
+namespace NTF;
+
+// Handle to a string interned by the engine, equal text always gets the same handle.
+// Intern once (e.g. into a static readonly field) and pass the handle around instead of strings.
+[StructLayout(LayoutKind.Sequential)]
+public readonly struct StringName : IEquatable<StringName>
+{
+    public static readonly StringName None = default;
+
+    public readonly uint Id;
+
+    private StringName(uint id)
+    {
+        Id = id;
+    }
+
+    public bool IsNone => Id == 0;
+
+    // For ids the engine hands back, which are always interned
+    internal static StringName FromId(uint id) => new(id);
+
+    public static unsafe StringName Intern(string value)
+    {
+        fixed (char* data = value)
+        {
+            return new StringName(StringNameInternal.InternWide((ushort*)data, (nuint)value.Length));
+        }
+    }
+
+    public static implicit operator StringName(string value) => Intern(value);
+
+    public override unsafe string ToString()
+    {
+        nuint len;
+        byte* data = StringNameInternal.GetUtf8(Id, &len);
+        return Encoding.UTF8.GetString(data, checked((int)len));
+    }
+
+    public bool Equals(StringName other) => Id == other.Id;
+    public override bool Equals(object? obj) => obj is StringName other && Equals(other);
+    public override int GetHashCode() => Id.GetHashCode();
+    public static bool operator ==(StringName a, StringName b) => a.Id == b.Id;
+    public static bool operator !=(StringName a, StringName b) => a.Id != b.Id;
+}
@@ -0,0 +1,46 @@
+using System;
+using GlmSharp;
+
+namespace NTF;
+
+// A 2D affine transform as a column-major 3x2 matrix, the fields are generated from the engine's
+// Transform2D so both sides share a layout. Same memory as GlmSharp's mat3x2.
+public partial struct Transform2D : IEquatable<Transform2D>
+{
+    public static readonly Transform2D Identity = new(new vec2(1, 0), new vec2(0, 1), new vec2(0, 0));
+
+    public Transform2D(vec2 xAxis, vec2 yAxis, vec2 translation)
+    {
+        XAxis = xAxis;
+        YAxis = yAxis;
+        Translation = translation;
+    }
+
+    public static Transform2D FromTranslation(vec2 translation)
+        => new(new vec2(1, 0), new vec2(0, 1), translation);
+
+    // Scales first, then rotates counter-clockwise by angle radians, then translates
+    public static Transform2D FromScaleAngleTranslation(vec2 scale, float angle, vec2 translation)
+    {
+        float sin = MathF.Sin(angle);
+        float cos = MathF.Cos(angle);
+        return new(new vec2(cos, sin) * scale.x, new vec2(-sin, cos) * scale.y, translation);
+    }
+
+    public vec2 TransformPoint(vec2 point) => XAxis * point.x + YAxis * point.y + Translation;
+
+    // a * b applies b first, like matrix multiplication
+    public static Transform2D operator *(Transform2D a, Transform2D b)
+        => new(a.XAxis * b.XAxis.x + a.YAxis * b.XAxis.y,
+               a.XAxis * b.YAxis.x + a.YAxis * b.YAxis.y,
+               a.TransformPoint(b.Translation));
+
+    public mat3x2 ToMat3x2() => new(XAxis.x, XAxis.y, YAxis.x, YAxis.y, Translation.x, Translation.y);
+
+    public bool Equals(Transform2D other)
+        => XAxis == other.XAxis && YAxis == other.YAxis && Translation == other.Translation;
+    public override bool Equals(object? obj) => obj is Transform2D other && Equals(other);
+    public override int GetHashCode() => HashCode.Combine(XAxis, YAxis, Translation);
+    public static bool operator ==(Transform2D a, Transform2D b) => a.Equals(b);
+    public static bool operator !=(Transform2D a, Transform2D b) => !a.Equals(b);
+}